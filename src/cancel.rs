@@ -0,0 +1,98 @@
+//! Содержит типы для отмены запросов, выполняющихся в соединении, из другого потока.
+
+use std::sync::{Arc, Mutex};
+
+use Result;
+
+use ffi::Handle;// Основные типобезопасные примитивы
+
+use ffi::native::{OCIError, OCISvcCtx};// FFI типы
+use ffi::native::break_;
+
+/// Хендлы соединения, необходимые для прерывания выполняющегося в нем запроса.
+#[derive(Debug)]
+struct Target {
+  /// Сервисный контекст соединения, запрос в котором требуется прервать. Хендлом владеет соединение.
+  context: Handle<OCISvcCtx>,
+  /// Отдельный хендл для приема ошибок прерывания, т.к. хендлом ошибок окружения в это время пользуется поток,
+  /// выполняющий запрос.
+  error: Handle<OCIError>,
+}
+// Хендлы используются только под защитой мьютекса и только до тех пор, пока соединение не отсоединит их при своем
+// закрытии, а OCI допускает вызов `OCIBreak()` из другого потока.
+unsafe impl Send for Target {}
+
+/// Объект для отмены запросов, выполняющихся в соединении, из другого потока. Создается вызовом метода
+/// [`Connection::cancel_handle()`][new].
+///
+/// В отличие от самого соединения, может быть передан в другой поток и пережить соединение и окружение. После
+/// закрытия соединения или разрушения окружения вызов [`cancel()`][cancel] ничего не делает.
+///
+/// Для того, чтобы соединением можно было пользоваться из нескольких потоков, окружение должно быть создано в режиме
+/// [`CreateMode::Threaded`][threaded].
+///
+/// # Пример
+/// ```no_run
+/// # use std::thread;
+/// # use std::time::Duration;
+/// # use oci::Environment;
+/// # use oci::error::Error;
+/// # use oci::params::{ConnectParams, Credentials};
+/// # use oci::types::CreateMode;
+/// let env = Environment::new(CreateMode::Threaded).unwrap();
+/// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+/// let handle = conn.cancel_handle().unwrap();
+/// thread::spawn(move || {
+///   thread::sleep(Duration::from_secs(1));
+///   handle.cancel().unwrap();
+/// });
+/// let stmt = conn.prepare("begin dbms_lock.sleep(10); end;").unwrap();
+/// match stmt.execute() {
+///   Err(Error::Cancelled) => println!("cancelled"),
+///   res => println!("{:?}", res),
+/// }
+/// ```
+///
+/// [new]: ../struct.Connection.html#method.cancel_handle
+/// [cancel]: #method.cancel
+/// [threaded]: ../types/enum.CreateMode.html#variant.Threaded
+#[derive(Clone, Debug)]
+pub struct CancelHandle {
+  /// Хендлы соединения, разделяемые со всеми копиями объекта. Соединение сбрасывает их в `None` при закрытии.
+  target: Arc<Mutex<Option<Target>>>,
+}
+impl CancelHandle {
+  /// Прерывает запрос, выполняющийся в соединении в данный момент. Прерванная функция (выполнение выражения или
+  /// извлечение строк) вернет ошибку [`Error::Cancelled`][1], после чего соединением можно продолжать пользоваться.
+  /// Если в соединении ничего не выполняется или оно уже закрыто, ничего не делает.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCIBreak()`][2].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу, если соединение еще не закрыто.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.Cancelled
+  /// [2]: http://docs.oracle.com/database/122/LNOCI/miscellaneous-functions.htm#LNOCI17285
+  pub fn cancel(&self) -> Result<()> {
+    let target = self.target.lock().unwrap_or_else(|e| e.into_inner());
+    match *target {
+      Some(ref t) => break_(&t.context, &t.error).map_err(Into::into),
+      None => Ok(()),
+    }
+  }
+}
+impl super::CancelHandlePrivate for CancelHandle {
+  fn new(context: *mut OCISvcCtx, error: Handle<OCIError>) -> Self {
+    let target = Target { context: Handle::borrowed(context), error: error };
+    CancelHandle { target: Arc::new(Mutex::new(Some(target))) }
+  }
+  fn detach(&self) {
+    let mut target = self.target.lock().unwrap_or_else(|e| e.into_inner());
+    // Хендл ошибок освобождается здесь, пока окружение, которому он принадлежит, еще живо
+    *target = None;
+  }
+  fn is_attached(&self) -> bool {
+    self.target.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+  }
+}
@@ -54,10 +54,12 @@ pub trait AsDB {
 //-------------------------------------------------------------------------------------------------
 
 impl<'conn> FromDB<'conn> for String {
+  #[allow(deprecated)]// Позволяем извлекать значения столбцов LONG, которые все еще встречаются в старых схемах
   fn from_db(ty: Type, raw: &[u8], _: &Connection) -> Result<Self> {
     match ty {
       Type::CHR |
-      Type::AFC => str::from_utf8(raw).map(str::to_owned).map_err(|_| Error::Conversion(Type::CHR)),
+      Type::AFC |
+      Type::LNG => str::from_utf8(raw).map(str::to_owned).map_err(|_| Error::Conversion(Type::CHR)),
       t => Err(Error::Conversion(t)),
     }
   }
@@ -97,7 +99,7 @@ fn from_ds(ty: Type, raw: &[u8], conn: &Connection) -> Result<Duration> {
 //-------------------------------------------------------------------------------------------------
 
 /// Содержит информацию, необходимую для обобщенного связывания любого типа, реализующего `Into<BindInfo>`.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct BindInfo<'a> {
   /// Указатель на начало памяти, содержащей данные для связывания.
   pub ptr: *const c_void,
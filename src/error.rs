@@ -3,11 +3,13 @@
 use std::convert::From;
 use std::error;
 use std::fmt;
+use std::io;
 
 use types::Type;
+use version::Version;
 
 /// Информация об одной ошибке/предупреждении Oracle
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Info {
   /// Код ошибки оракла, `ORA-xxxxx`.
   pub code: isize,
@@ -15,6 +17,22 @@ pub struct Info {
   pub message: String,
 }
 
+/// Сообщение компилятора PL/SQL (ошибка или предупреждение), полученное для хранимого объекта из представления
+/// `ALL_ERRORS`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+  /// Номер строки в исходном тексте объекта (нумерация с 1), к которой относится сообщение.
+  pub line: u32,
+  /// Номер позиции в строке (нумерация с 1), к которой относится сообщение.
+  pub column: u32,
+  /// Код сообщения, например, `201` для `PLS-00201`.
+  pub code: u32,
+  /// Текст сообщения.
+  pub text: String,
+  /// Признак того, что сообщение является предупреждением, а не ошибкой компиляции.
+  pub warning: bool,
+}
+
 /// Ошибки, возникающие при вызове нативных функций Oracle,
 #[derive(Debug)]
 pub enum DbError {
@@ -72,6 +90,11 @@ pub enum Error {
   /// [get]: ../stmt/struct.Row.html#method.get
   /// [row]: ../stmt/struct.Row.html
   InvalidColumn,
+  /// Описатель выходной переменной или PL/SQL массива, переданный в метод выражения (например, [`get_out()`][get_out]),
+  /// был получен при связывании с другим выражением.
+  ///
+  /// [get_out]: ../stmt/struct.Statement.html#method.get_out
+  InvalidParam,
   /// Ошибка невозможности записать все данные, извлеченные из базы, в предоставленный буфер.
   Overflow {
     /// Количество байт, прочитанное из базы, это число больше поля `capacity`.
@@ -79,6 +102,113 @@ pub enum Error {
     /// Вместимость буфера, это число меньше поля `extracted`.
     capacity: usize,
   },
+  /// Переданное имя (например, имя [точки сохранения][savepoint] транзакции) не является корректным
+  /// идентификатором Oracle и не может быть подставлено в текст запроса.
+  ///
+  /// [savepoint]: ../transaction/struct.Transaction.html#method.savepoint
+  InvalidIdentifier(String),
+  /// Количество итераций, запрошенное при [массивном выполнении][many] выражения, превышает количество элементов
+  /// в одном из [связанных массивов][array]. Переменные, связанные не с массивами, а с единственным значением
+  /// (например, [выходные переменные][out]), считаются массивами из одного элемента.
+  ///
+  /// [many]: ../stmt/struct.Statement.html#method.execute_many
+  /// [array]: ../stmt/struct.Statement.html#method.bind_array
+  /// [out]: ../stmt/struct.Statement.html#method.bind_out
+  ArrayTooShort {
+    /// Запрошенное количество итераций.
+    iters: usize,
+    /// Количество элементов в самом коротком из связанных массивов.
+    len: usize,
+  },
+  /// Ошибка разбора текста выражения сервером при его [проверке][validate] или [описании][describe]. Потеря
+  /// соединения и отмена запроса не считаются ошибками разбора и возвращаются как [`Db`][db] или
+  /// [`Cancelled`][cancelled].
+  ///
+  /// [db]: #variant.Db
+  /// [cancelled]: #variant.Cancelled
+  /// [validate]: ../stmt/struct.Statement.html#method.validate
+  /// [describe]: ../stmt/struct.Statement.html#method.describe
+  Parse {
+    /// Смещение в тексте выражения (нумерация с 0), в котором сервер обнаружил ошибку. Значение `0` означает ошибку
+    /// в первой лексеме выражения.
+    offset: usize,
+    /// Код и сообщение об ошибке.
+    info: Info,
+  },
+  /// Хранимый объект (процедура, пакет, представление и т.п.) создан или перекомпилирован, но содержит ошибки
+  /// компиляции (`ORA-24344`), и поэтому не может использоваться. Объект, скомпилированный только с предупреждениями,
+  /// ошибкой не считается, предупреждения доступны через [`Statement::compile_warnings()`][warnings].
+  ///
+  /// [warnings]: ../stmt/struct.Statement.html#method.compile_warnings
+  Compile {
+    /// Имя объекта в том виде, в котором оно хранится в словаре данных.
+    object: String,
+    /// Сообщения компилятора для объекта в порядке их следования.
+    diagnostics: Vec<Diagnostic>,
+  },
+  /// Выполнение выражения или извлечение строк было прервано вызовом [`CancelHandle::cancel()`][cancel] из другого
+  /// потока (`ORA-01013`). Соединение после этого остается пригодным для работы.
+  ///
+  /// [cancel]: ../cancel/struct.CancelHandle.html#method.cancel
+  Cancelled,
+  /// Ошибка чтения данных из читателя, связанного с выражением методом [`bind_reader()`][reader] или
+  /// [`bind_text_reader()`][text], из-за которой было прервано выполнение выражения.
+  ///
+  /// [reader]: ../stmt/struct.Statement.html#method.bind_reader
+  /// [text]: ../stmt/struct.Statement.html#method.bind_text_reader
+  Io(io::Error),
+  /// Объект для отмены запросов [запрошен][cancel] у соединения или [пул сессий][pool] создается в окружении,
+  /// созданном не в режиме [`CreateMode::Threaded`][threaded], и поэтому не может использоваться из другого потока.
+  ///
+  /// [cancel]: ../struct.Connection.html#method.cancel_handle
+  /// [pool]: ../pool/struct.SessionPool.html
+  /// [threaded]: ../types/enum.CreateMode.html#variant.Threaded
+  NotThreaded,
+  /// Для соединения уже существует объект [транзакции][tx], поэтому создать еще один или зафиксировать/откатить
+  /// транзакцию методами самого соединения нельзя.
+  ///
+  /// [tx]: ../transaction/struct.Transaction.html
+  TransactionInProgress,
+  /// При извлечении значения `LONG` или `LONG RAW` по частям функция `OCIStmtGetPieceInfo()` сообщила о запросе
+  /// части с неизвестным кодом. Содержит код части, полученный от OCI.
+  UnknownPiece(u8),
+  /// Запрошенная возможность требует клиентской библиотеки Oracle более новой версии, чем [используемая][client].
+  ///
+  /// [client]: ../version/fn.client_version.html
+  UnsupportedClient {
+    /// Минимальная версия клиента, поддерживающая возможность.
+    required: Version,
+    /// Версия используемой клиентской библиотеки.
+    client: Version,
+  },
+}
+/// Коды ошибок Oracle, означающие, что сессия или сетевое соединение с сервером потеряны и продолжить работу
+/// можно только после повторного подключения.
+const CONNECTION_LOST: &'static [isize] = &[
+  28,    // ORA-00028: your session has been killed
+  1012,  // ORA-01012: not logged on
+  1033,  // ORA-01033: ORACLE initialization or shutdown in progress
+  1034,  // ORA-01034: ORACLE not available
+  1089,  // ORA-01089: immediate shutdown in progress
+  1092,  // ORA-01092: ORACLE instance terminated. Disconnection forced
+  2396,  // ORA-02396: exceeded maximum idle time, please connect again
+  3113,  // ORA-03113: end-of-file on communication channel
+  3114,  // ORA-03114: not connected to ORACLE
+  3135,  // ORA-03135: connection lost contact
+  12537, // ORA-12537: TNS:connection closed
+  12547, // ORA-12547: TNS:lost contact
+  12570, // ORA-12570: TNS:packet reader failure
+  12571, // ORA-12571: TNS:packet writer failure
+];
+impl Error {
+  /// Проверяет, означает ли ошибка потерю сессии или сетевого соединения с сервером (например, `ORA-03113` или
+  /// `ORA-03135`). После такой ошибки соединение непригодно для дальнейшей работы и его необходимо установить заново.
+  pub fn is_connection_lost(&self) -> bool {
+    match *self {
+      Error::Db(DbError::Fault(ref info)) => CONNECTION_LOST.contains(&info.code),
+      _ => false,
+    }
+  }
 }
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -91,18 +221,34 @@ impl error::Error for Error {
       Error::Db(ref err) => err.description(),
       Error::Conversion(_) => "Can't convert value from/to Rust to DB type",
       Error::InvalidColumn => "Non-existing column",
+      Error::InvalidParam => "Parameter handle belongs to another statement",
       Error::Overflow { .. } => "Not enough buffer size for store database result",
+      Error::InvalidIdentifier(_) => "Invalid Oracle identifier",
+      Error::ArrayTooShort { .. } => "Bound array contains fewer elements than execution iterations",
+      Error::Parse { ref info, .. } => &info.message,
+      Error::Compile { .. } => "Stored object compiled with errors",
+      Error::Cancelled => "Operation cancelled by user request",
+      Error::Io(ref err) => err.description(),
+      Error::NotThreaded => "Environment is not created in threaded mode",
+      Error::TransactionInProgress => "Connection already has an active transaction object",
+      Error::UnknownPiece(_) => "OCIStmtGetPieceInfo() returned unknown piece code",
+      Error::UnsupportedClient { .. } => "Feature is not supported by Oracle client library version",
     }
   }
   fn cause(&self) -> Option<&error::Error> {
     match *self {
       Error::Db(ref err) => Some(err),
+      Error::Io(ref err) => Some(err),
       _ => None,
     }
   }
 }
 impl From<DbError> for Error {
   fn from(err: DbError) -> Self {
-    Error::Db(err)
+    match err {
+      // ORA-01013: user requested cancel of current operation
+      DbError::Fault(Info { code: 1013, .. }) => Error::Cancelled,
+      err => Error::Db(err),
+    }
   }
 }
\ No newline at end of file
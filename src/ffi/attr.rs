@@ -68,6 +68,14 @@ pub trait AttrHolder<T> {
       Ok(cstr.into_string().expect("OCIAttrGet call returns non UTF-8 string"))
     }
   }
+  /// Получает хендл-атрибут хендла. Полученный указатель принадлежит владельцу атрибута.
+  fn get_handle<U: HandleType>(&self, attrtype: types::Attr, err: &Handle<OCIError>) -> DbResult<*mut U> {
+    let mut res: *mut U = ptr::null_mut();
+    let ptr = &mut res as *mut *mut U;
+    try!(unsafe { self.get(ptr as *mut c_void, &mut 0, attrtype, err) });
+
+    Ok(res)
+  }
 //-------------------------------------------------------------------------------------------------
   fn set_<I: Integer>(&mut self, value: I, attrtype: types::Attr, err: &Handle<OCIError>) -> DbResult<()> {
     let ptr = &value as *const I;
@@ -3,7 +3,6 @@ use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::{c_int, c_void};
 use std::ptr;
-use std::slice;
 
 use {Environment, DbResult};
 
@@ -18,8 +17,9 @@ use ffi::types;
 fn close<T>(native: *const T, id: types::Descriptor) {
   let res = unsafe { OCIDescriptorFree(native as *mut c_void, id as u32) };
   //FIXME: Необходимо получать точную причину ошибки, а для этого нужна ссылка на OCIError.
-  // Однако тащить ее в дескриптор нельзя, т.к. данная структура должна быть легкой
-  check(res).expect("OCIDescriptorFree");
+  // Однако тащить ее в дескриптор нельзя, т.к. данная структура должна быть легкой.
+  // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+  let _ = check(res);
 }
 
 //-------------------------------------------------------------------------------------------------
@@ -29,7 +29,14 @@ pub struct Descriptor<'d, T: 'd + DescriptorType> {
   phantom: PhantomData<&'d T>,
 }
 impl<'d, T: 'd + DescriptorType> Descriptor<'d, T> {
-  pub fn new(env: &'d Environment) -> DbResult<Self> {
+  /// Создает новый дескриптор в указанном окружении
+  ///
+  /// # Параметры
+  /// - `env`:
+  ///   Окружение, которое будет владеть созданным дескриптором
+  /// - `err`:
+  ///   Хендл для сбора ошибок, куда будет записана ошибка в случае, если создание дескриптора окажется неудачным
+  pub fn new(env: &'d Environment, err: &Handle<OCIError>) -> DbResult<Self> {
     let mut desc = ptr::null_mut();
     let res = unsafe {
       OCIDescriptorAlloc(
@@ -38,7 +45,7 @@ impl<'d, T: 'd + DescriptorType> Descriptor<'d, T> {
         0, 0 as *mut *mut c_void// размер пользовательских данных и указатель на выделенное под них место
       )
     };
-    Self::from_ptr(res, desc as *const T, env.error())
+    Self::from_ptr(res, desc as *const T, err)
   }
   pub fn from_ptr(res: c_int, native: *const T, err: &Handle<OCIError>) -> DbResult<Self> {
     match res {
@@ -70,55 +77,34 @@ impl<'d, T: 'd + DescriptorType> AttrHolder<T> for Descriptor<'d, T> {
   }
 }
 
-/// Нетипизированный автоматически закрываемый дескриптор оракла.
-pub struct GenericDescriptor<'d> {
+/// Нетипизированный автоматически закрываемый дескриптор оракла. Как и хендл, не отслеживает время жизни окружения,
+/// из которого выделен, поэтому его владелец должен сам нести время жизни исходного дескриптора (см. `stmt::storage::Storage`).
+pub struct GenericDescriptor {
   native: *const c_void,
   id: types::Descriptor,
-  phantom: PhantomData<&'d c_void>,
 }
-impl<'d> GenericDescriptor<'d> {
-  /// Получает указатель на начало памяти, в которой лежит указатель на данные дескриптора
-  #[inline]
-  pub fn address_mut(&mut self) -> *mut c_void {
-    &mut self.native as *mut *const c_void as *mut c_void
-  }
-  /// Получает срез, представляющий кусок памяти, в котором лежит указатель на данные дескриптора
-  #[inline]
-  pub fn as_slice(&self) -> &[u8] {
-    unsafe {
-      slice::from_raw_parts(
-        &self.native as *const *const c_void as *const u8,
-        mem::size_of::<*const c_void>()
-      )
-    }
-  }
+impl GenericDescriptor {
   /// Получает типизированный raw-указатель на данные в дескрипторе, если тип запрошенного указателя
   /// совпадает с реальным типом, хранящемся в дескрипторе. В противном случае возвращает `None`.
   #[inline]
   pub fn native<T: DescriptorType>(&self) -> Option<*const T> {
     if T::ID == self.id { Some(self.native as *const T) } else { None }
   }
-  /// Получает типизированный raw-указатель на данные в дескрипторе, если тип запрошенного указателя
-  /// совпадает с реальным типом, хранящемся в дескрипторе. В противном случае возвращает `None`.
-  #[inline]
-  pub fn native_mut<T: DescriptorType>(&self) -> Option<*mut T> {
-    if T::ID == self.id { Some(self.native as *mut T) } else { None }
-  }
 }
-impl<'d, T: DescriptorType> From<Descriptor<'d, T>> for GenericDescriptor<'d> {
+impl<'d, T: DescriptorType> From<Descriptor<'d, T>> for GenericDescriptor {
   fn from(d: Descriptor<'d, T>) -> Self {
-    let res = GenericDescriptor { native: d.native as *const c_void, id: T::ID, phantom: PhantomData };
+    let res = GenericDescriptor { native: d.native as *const c_void, id: T::ID };
     // Дескриптор уходит в небытие, чтобы он не закрыл ресурс, забываем его
     mem::forget(d);
     res
   }
 }
-impl<'d> Drop for GenericDescriptor<'d> {
+impl Drop for GenericDescriptor {
   fn drop(&mut self) {
     close(self.native, self.id);
   }
 }
-impl<'d> fmt::Debug for GenericDescriptor<'d> {
+impl fmt::Debug for GenericDescriptor {
   fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
     fmt.debug_tuple("GenericDescriptor")
        .field(&self.id)
@@ -1,6 +1,8 @@
 //! Содержит реализацию автоматически закрываемого хендла окружения
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::c_void;
 use std::ptr;
 
 use DbResult;
@@ -10,7 +12,7 @@ use ffi::{check, decode_error, Handle};// Основные типобезопа
 use ffi::{ErrorHandle, HandleType};// Типажи для безопасного моста к FFI
 
 use ffi::native::{OCIEnv, OCIError};// FFI типы
-use ffi::native::{OCIEnvNlsCreate, OCITerminate};// FFI функции
+use ffi::native::{OCIEnvNlsCreate, OCIHandleFree, OCITerminate};// FFI функции
 
 //-------------------------------------------------------------------------------------------------
 /// Автоматически закрываемый хендл окружения оракла
@@ -51,15 +53,30 @@ impl<'e> Env<'e> {
   pub fn new_handle<T: HandleType, E: ErrorHandle>(&self, err: *mut E) -> DbResult<Handle<T>> {
     Handle::new(&self, err)
   }
+  /// Создает новый хендл для сбора ошибок. Ошибка его создания записывается в сам хендл окружения, поэтому
+  /// хендл можно создавать одновременно из нескольких потоков.
   #[inline]
-  pub fn new_error_handle(&mut self) -> DbResult<Handle<OCIError>> {
+  pub fn new_error_handle(&self) -> DbResult<Handle<OCIError>> {
     self.new_handle(self.native as *mut OCIEnv)
   }
+  /// Получает параметры, с которыми было инициализировано данное окружение.
+  #[inline]
+  pub fn params(&self) -> &InitParams {
+    &self.params
+  }
   /// Получает голый указатель на хендл окружения, используемый для передачи в нативные функции.
   #[inline]
   pub fn native(&self) -> *const OCIEnv {
     self.native
   }
+  /// Освобождает хендл окружения вместе со всеми хендлами, созданными в нем, возвращая ошибку, если освободить его
+  /// не удалось.
+  pub fn close(self) -> DbResult<()> {
+    let res = unsafe { OCIHandleFree(self.native as *mut c_void, <OCIEnv as ErrorHandle>::ID as u32) };
+    // Хендл уже освобожден, повторное освобождение при разрушении не требуется
+    mem::forget(self);
+    check(res)
+  }
   /// Данная функция существует по той причине, что ее вызов при разрушения данного объекта приведет к невозможности заново создать
   /// данный объект, т.к. повторная инициализация окружения вызывает crash в недрах OCI. По этому поводу еще с 2015 года [есть вопрос][1]
   /// на официальном форуме сообщества Oracle, который был проигнорирован.
@@ -83,6 +100,13 @@ impl<'e> Env<'e> {
     check(res)
   }
 }
+impl<'e> Drop for Env<'e> {
+  fn drop(&mut self) {
+    let res = unsafe { OCIHandleFree(self.native as *mut c_void, <OCIEnv as ErrorHandle>::ID as u32) };
+    // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+    let _ = check(res);
+  }
+}
 impl<'e> fmt::Debug for Env<'e> {
   fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
     fmt.debug_tuple("Env")
@@ -1,11 +1,12 @@
 use std::fmt;
+use std::mem;
 use std::os::raw::{c_int, c_void};
 use std::ptr;
 
 use DbResult;
-use error::DbError;
+use error::{DbError, Info};
 
-use ffi::{check, decode_error, Env};// Основные типобезопасные примитивы
+use ffi::{check, decode_error, decode_error_piece, Env};// Основные типобезопасные примитивы
 use ffi::{ErrorHandle, HandleType};// Типажи для безопасного моста к FFI
 
 use ffi::attr::AttrHolder;
@@ -16,6 +17,9 @@ use ffi::native::{OCIHandleAlloc, OCIHandleFree};// FFI функции
 /// Автоматически освобождаемый хендл на ресурсы оракла
 pub struct Handle<T: HandleType> {
   native: *mut T,
+  /// Признак того, что хендл владеет ресурсом и должен освободить его при разрушении. Хендлы, временем жизни
+  /// которых управляет сам OCI (например, хендлы сессий, полученных из пула), не освобождаются.
+  owned: bool,
 }
 impl<T: HandleType> Handle<T> {
   /// Создает новый хендл в указанном окружении
@@ -38,21 +42,42 @@ impl<T: HandleType> Handle<T> {
   }
   pub fn from_ptr<E: ErrorHandle>(res: c_int, native: *mut T, err: *mut E) -> DbResult<Handle<T>> {
     match res {
-      0 => Ok(Handle { native: native }),
+      0 => Ok(Handle { native: native, owned: true }),
       e => Err(decode_error(err, e)),
     }
   }
+  /// Оборачивает хендл, временем жизни которого управляет OCI. Такой хендл не будет освобожден при разрушении
+  /// обертки.
+  pub fn borrowed(native: *mut T) -> Handle<T> {
+    Handle { native: native, owned: false }
+  }
   #[inline]
   pub fn native_mut(&self) -> *mut T {
     self.native
   }
+  /// Освобождает хендл, возвращая ошибку, если освободить его не удалось. В отличие от разрушения хендла, которое
+  /// игнорирует ошибки, позволяет узнать о них.
+  pub fn free(self) -> DbResult<()> {
+    let res = if self.owned {
+      unsafe { OCIHandleFree(self.native as *mut c_void, T::ID as u32) }
+    } else {
+      0
+    };
+    // Хендл уже освобожден, повторное освобождение при разрушении не требуется
+    mem::forget(self);
+    check(res)
+  }
 }
 impl<T: HandleType> Drop for Handle<T> {
   fn drop(&mut self) {
+    if !self.owned {
+      return;
+    }
     let res = unsafe { OCIHandleFree(self.native as *mut c_void, T::ID as u32) };
     //FIXME: Необходимо получать точную причину ошибки, а для этого нужна ссылка на OCIError.
-    // Однако тащить ее в хендл нельзя, т.к. данная структура должна быть легкой
-    check(res).expect("OCIHandleFree");
+    // Однако тащить ее в хендл нельзя, т.к. данная структура должна быть легкой.
+    // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+    let _ = check(res);
   }
 }
 impl<T: HandleType> fmt::Debug for Handle<T> {
@@ -78,6 +103,12 @@ impl Handle<OCIError> {
   pub fn decode(&self, result: c_int) -> DbError {
     decode_error(self.native, result)
   }
+  /// Получает информацию о первой ошибке, записанной в данный хендл. Используется для хендлов, полученных не в
+  /// результате неудачного вызова функции, а извлеченных готовыми (например, ошибок отдельных итераций в режиме
+  /// пакетных ошибок).
+  pub fn info(&self) -> Info {
+    decode_error_piece(self.native, 1).1
+  }
   pub fn check(&self, result: c_int) -> DbResult<()> {
     match result {
       0 => Ok(()),
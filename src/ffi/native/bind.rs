@@ -3,13 +3,17 @@
 //!
 //! [1]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI153
 
+use std::cell::{Ref, RefCell};
 use std::fmt;
 use std::mem;
 use std::os::raw::{c_int, c_void};
 use std::ptr;
 
+use ffi::Handle;// Основные типобезопасные примитивы
+use ffi::attr::AttrHolder;
+
 use ffi::native::{OCIBind, OCISvcCtx, OCIDefine, OCIDescribe, OCIError, OCIStmt, OCIType};// FFI типы
-use ffi::types::{CallbackResult, OCIInd, Piece};
+use ffi::types::{Attr, CallbackResult, OCIInd, Piece};
 
 
 pub type OCICallbackInBind  = extern "C" fn(ictxp: *mut c_void,
@@ -113,6 +117,124 @@ pub extern "C" fn in_bind_adapter(ictxp: *mut c_void,
 
   (if res { CallbackResult::Done } else { CallbackResult::Continue }) as i32
 }
+/// Значение, записанное сервером в выходную переменную, связанную динамически, для одной строки.
+#[derive(Debug)]
+pub struct OutValue {
+  /// Буфер, в который сервер записывает значение.
+  data: Vec<u8>,
+  /// Реальная длина записанного значения.
+  len: u32,
+  /// Индикатор `NULL`-а значения.
+  is_null: i16,
+  /// Код возврата для значения.
+  ret_code: u16,
+}
+impl OutValue {
+  /// Возвращает записанное значение в виде среза из массива байт, или `None`, если сервер записал `NULL`.
+  #[inline]
+  pub fn as_slice(&self) -> Option<&[u8]> {
+    match self.is_null {
+      0 => Some(&self.data[..self.len as usize]),
+      _ => None,
+    }
+  }
+}
+/// Содержит значения выходной переменной, связанной динамически, например, переменной в предложении
+/// `RETURNING ... INTO` DML выражения. Сервер может вернуть в такую переменную несколько значений на каждую
+/// итерацию выполнения выражения, поэтому буферы для них выделяются по запросу сервера.
+#[derive(Debug)]
+pub struct OutBindContext {
+  /// Размер буфера, выделяемого под каждое значение.
+  elem_size: usize,
+  /// Хендл для получения ошибок при запросе количества возвращаемых строк. Хендлом владеет соединение.
+  error: *mut OCIError,
+  /// Индикатор `NULL`-а, передаваемый серверу в качестве входного значения переменной.
+  in_null: OCIInd,
+  /// Значения для каждой итерации выполнения, для каждой строки. Значения хранятся в куче, т.к. сервер
+  /// записывает в них данные уже после возврата из функции обратного вызова.
+  rows: RefCell<Vec<Vec<Box<OutValue>>>>,
+}
+impl OutBindContext {
+  pub fn new(elem_size: usize, error: *mut OCIError) -> Self {
+    OutBindContext { elem_size: elem_size, error: error, in_null: OCIInd::Null, rows: RefCell::new(Vec::new()) }
+  }
+  /// Удаляет значения, полученные при предыдущем выполнении выражения.
+  #[inline]
+  pub fn clear(&self) {
+    self.rows.borrow_mut().clear();
+  }
+  /// Получает значения, записанные сервером при последнем выполнении выражения, для каждой итерации выполнения.
+  #[inline]
+  pub fn rows(&self) -> Ref<Vec<Vec<Box<OutValue>>>> {
+    self.rows.borrow()
+  }
+}
+/// Функция, предоставляющая серверу входное значение для выходной переменной, связанной динамически.
+/// Всегда возвращает `NULL`, как того требует API для переменных в предложении `RETURNING ... INTO`.
+pub extern "C" fn null_in_bind_adapter(ictxp: *mut c_void,
+                                       _bindp: *mut OCIBind,
+                                       _iter: u32,
+                                       _index: u32,
+                                       bufpp: *mut *mut c_void,
+                                       alenp: *mut u32,
+                                       piecep: *mut u8,
+                                       indpp: *mut *mut c_void) -> i32 {
+  let ctx: &OutBindContext = unsafe { &*(ictxp as *const OutBindContext) };
+  unsafe {
+    if !bufpp.is_null() { *bufpp = ptr::null_mut(); }
+    if !alenp.is_null() { *alenp = 0; }
+    if !indpp.is_null() { *indpp = &ctx.in_null as *const _ as *const c_void as *mut c_void; }
+    if !piecep.is_null(){ *piecep= Piece::One as u8; }
+  }
+  CallbackResult::Continue as i32
+}
+/// Функция, выделяющая буфер для очередного значения выходной переменной, связанной динамически, по запросу сервера.
+pub extern "C" fn out_bind_adapter(octxp: *mut c_void,
+                                   bindp: *mut OCIBind,
+                                   iter: u32,
+                                   index: u32,
+                                   bufpp: *mut *mut c_void,
+                                   alenpp: *mut *mut u32,
+                                   piecep: *mut u8,
+                                   indpp: *mut *mut c_void,
+                                   rcodepp: *mut *mut u16) -> i32 {
+  let ctx: &OutBindContext = unsafe { &*(octxp as *const OutBindContext) };
+  let mut rows = ctx.rows.borrow_mut();
+  while rows.len() <= iter as usize {
+    rows.push(Vec::new());
+  }
+  // Перед первым значением итерации сервер сообщает, сколько строк она вернула. Если ни одной, то буфер все равно
+  // запрашивается, и в ответ необходимо передать пустые указатели
+  if index == 0 {
+    let returned: Result<u32, _> = Handle::borrowed(bindp).get_(Attr::RowsReturned, &Handle::borrowed(ctx.error));
+    if let Ok(0) = returned {
+      unsafe {
+        if !bufpp.is_null()  { *bufpp  = ptr::null_mut(); }
+        if !alenpp.is_null() { *alenpp = ptr::null_mut(); }
+        if !indpp.is_null()  { *indpp  = ptr::null_mut(); }
+        if !rcodepp.is_null(){ *rcodepp= ptr::null_mut(); }
+        if !piecep.is_null() { *piecep = Piece::One as u8; }
+      }
+      return CallbackResult::Continue as i32;
+    }
+  }
+  let values = &mut rows[iter as usize];
+  while values.len() <= index as usize {
+    values.push(Box::new(OutValue { data: vec![0; ctx.elem_size], len: 0, is_null: OCIInd::Null as i16, ret_code: 0 }));
+  }
+  let value = &mut values[index as usize];
+  value.len = ctx.elem_size as u32;
+
+  unsafe {
+    if !bufpp.is_null()  { *bufpp  = value.data.as_mut_ptr() as *mut c_void; }
+    if !alenpp.is_null() { *alenpp = &mut value.len; }
+    if !indpp.is_null()  { *indpp  = &mut value.is_null as *mut i16 as *mut c_void; }
+    if !rcodepp.is_null(){ *rcodepp= &mut value.ret_code; }
+    if !piecep.is_null() { *piecep = Piece::One as u8; }
+  }
+
+  CallbackResult::Continue as i32
+}
 // По странной прихоти разработчиков оракла на разных системах имя библиотеки разное
 #[cfg_attr(windows, link(name = "oci"))]
 #[cfg_attr(not(windows), link(name = "clntsh"))]
@@ -317,6 +439,35 @@ extern "C" {
                         objtyp: u8,
                         dschp: *mut OCIDescribe) -> c_int;
 
+  /// Gets the bind and indicator variable names.
+  ///
+  /// # Parameters
+  /// - stmtp (IN):
+  ///   Prepared statement handle.
+  /// - errhp (IN/OUT):
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - size (IN):
+  ///   The number of elements in each array.
+  /// - startloc (IN):
+  ///   Position of the bind variable at which to start getting bind information (1-based).
+  /// - found (IN):
+  ///   `abs(found)` gives the total number of bind variables in the statement, irrespective of the start position.
+  ///   Positive value if the number of bind variables returned is less than the size provided, otherwise negative.
+  /// - bvnp (OUT):
+  ///   Array of pointers to hold bind variable names.
+  /// - bvnl (OUT):
+  ///   Array to hold the length of the each `bvnp` element.
+  /// - invp (OUT):
+  ///   Array of pointers to hold indicator variable names.
+  /// - inpl (OUT):
+  ///   Array of pointers to hold the length of the each `invp` element.
+  /// - dupl (OUT):
+  ///   An array whose element value is `0` or `1` depending on whether the bind position is duplicate of another.
+  /// - hndl (OUT):
+  ///   An array that returns the bind handle if binds have been done for the bind position. No handle is returned
+  ///   for duplicates.
+  ///
+  /// Returns `OCI_NO_DATA` if the statement has no bind variables.
   pub fn OCIStmtGetBindInfo(stmtp: *mut OCIStmt,
                             errhp: *mut OCIError,
                             size: u32,
@@ -6,7 +6,7 @@
 use std::os::raw::{c_int, c_void};
 
 use ffi::types;
-use ffi::native::{OCIEnv, OCIError, OCIServer, OCISession, OCISvcCtx};// FFI типы
+use ffi::native::{OCICPool, OCIEnv, OCIError, OCIServer, OCISession, OCISPool, OCISvcCtx};// FFI типы
 
 // По странной прихоти разработчиков оракла на разных системах имя библиотеки разное
 #[cfg_attr(windows, link(name = "oci"))]
@@ -143,4 +143,187 @@ extern "C" {
                        errhp: *mut OCIError,
                        usrhp: *mut OCISession,
                        mode: u32) -> c_int;
+
+  /// Initializes the connection pool.
+  ///
+  /// # Comments
+  /// The connection pool is a pool of physical connections to the server. Sessions attached with `OCIServerAttach()`
+  /// in mode `OCI_CPOOL` to the pool name returned in `poolName` are multiplexed over these connections.
+  ///
+  /// # Parameters
+  /// - envhp:
+  ///   A pointer to the environment where the connection pool is to be created.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - poolhp:
+  ///   An allocated pool handle.
+  /// - poolName:
+  ///   The name of the connection pool connected to.
+  /// - poolNameLen:
+  ///   The length of the string pointed to by `poolName`.
+  /// - dblink:
+  ///   Specifies the database (server) to connect to.
+  /// - dblinkLen:
+  ///   The length of the string pointed to by `dblink`.
+  /// - connMin:
+  ///   Specifies the minimum number of connections in the connection pool. Valid values are 0 and higher.
+  /// - connMax:
+  ///   Specifies the maximum number of connections that can be opened to the database. After this value is reached,
+  ///   no more connections are opened. Valid values are 1 and higher.
+  /// - connIncr:
+  ///   Allows the application to set the next increment for connections to be opened to the database if the current
+  ///   number of connections is less than `connMax`. Valid values are 0 and higher.
+  /// - poolUserName, poolUserLen:
+  ///   Connection pooling requires an implicit primary session and this attribute provides a user name for that
+  ///   session, and its length.
+  /// - poolPassword, poolPassLen:
+  ///   The password for the parameter `poolUserName`, and its length.
+  /// - mode:
+  ///   The modes supported are `OCI_DEFAULT` and `OCI_CPOOL_REINITIALIZE`.
+  pub fn OCIConnectionPoolCreate(envhp: *mut OCIEnv,
+                                 errhp: *mut OCIError,
+                                 poolhp: *mut OCICPool,
+                                 poolName: *mut *const u8,// результат
+                                 poolNameLen: *mut i32,   // результат
+                                 dblink: *const u8,
+                                 dblinkLen: i32,
+                                 connMin: u32,
+                                 connMax: u32,
+                                 connIncr: u32,
+                                 poolUserName: *const u8,
+                                 poolUserLen: i32,
+                                 poolPassword: *const u8,
+                                 poolPassLen: i32,
+                                 mode: u32) -> c_int;
+  /// Destroys the connection pool.
+  ///
+  /// # Parameters
+  /// - poolhp:
+  ///   A pool handle for a connection pool.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - mode:
+  ///   Currently, `OCIConnectionPoolDestroy()` supports only the `OCI_DEFAULT` mode.
+  pub fn OCIConnectionPoolDestroy(poolhp: *mut OCICPool,
+                                  errhp: *mut OCIError,
+                                  mode: u32) -> c_int;
+
+  /// Creates a session pool.
+  ///
+  /// # Comments
+  /// A session pool is a pool of stateless sessions that can be shared by multiple threads. The pool is identified
+  /// by the name returned in `poolName`, which is used later in `OCISessionGet()` calls.
+  ///
+  /// # Parameters
+  /// - envhp:
+  ///   A pointer to the environment where the session pool is to be created.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - spoolhp:
+  ///   A pointer to the session pool handle that is created.
+  /// - poolName:
+  ///   The name of the session pool returned. It is unique across all session pools in an environment. This value
+  ///   must be passed to the `OCISessionGet()` call.
+  /// - poolNameLen:
+  ///   Length of `poolName` in bytes.
+  /// - connStr:
+  ///   The TNS alias of the database to connect to.
+  /// - connStrLen:
+  ///   The length of `connStr` in bytes.
+  /// - sessMin:
+  ///   Specifies the minimum number of sessions in the session pool.
+  /// - sessMax:
+  ///   Specifies the maximum number of sessions in the session pool.
+  /// - sessIncr:
+  ///   Allows the application to set the next increment for sessions to be started if the current number of sessions
+  ///   is less than `sessMax`.
+  /// - userid, useridLen:
+  ///   Specifies the user name and its length in bytes. Used for homogeneous pools only.
+  /// - password, passwordLen:
+  ///   Specifies the password and its length in bytes. Used for homogeneous pools only.
+  /// - mode:
+  ///   The modes supported are `OCI_DEFAULT`, `OCI_SPC_REINITIALIZE`, `OCI_SPC_HOMOGENEOUS`, `OCI_SPC_STMTCACHE`
+  ///   and `OCI_SPC_NO_RLB`.
+  pub fn OCISessionPoolCreate(envhp: *mut OCIEnv,
+                              errhp: *mut OCIError,
+                              spoolhp: *mut OCISPool,
+                              poolName: *mut *const u8,// результат
+                              poolNameLen: *mut u32,   // результат
+                              connStr: *const u8,
+                              connStrLen: u32,
+                              sessMin: u32,
+                              sessMax: u32,
+                              sessIncr: u32,
+                              userid: *const u8,
+                              useridLen: u32,
+                              password: *const u8,
+                              passwordLen: u32,
+                              mode: u32) -> c_int;
+  /// Destroys a session pool.
+  ///
+  /// # Parameters
+  /// - spoolhp:
+  ///   The session pool handle to destroy.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - mode:
+  ///   Currently, `OCISessionPoolDestroy()` supports modes `OCI_DEFAULT` and `OCI_SPD_FORCE`. If this call is made
+  ///   with mode set to `OCI_SPD_FORCE`, and a session is busy, then the session is closed.
+  pub fn OCISessionPoolDestroy(spoolhp: *mut OCISPool,
+                               errhp: *mut OCIError,
+                               mode: u32) -> c_int;
+  /// Gets a session. This session may be a new one with a new underlying connection, or one that is started over a
+  /// virtual circuit from an existing connection pool, or one from an existing session pool.
+  ///
+  /// # Parameters
+  /// - envhp:
+  ///   OCI environment handle.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - svchp:
+  ///   Address of an OCI service context pointer. This is filled with a server and session handle. The service
+  ///   context handle is deallocated by the call `OCISessionRelease()`.
+  /// - authInfop:
+  ///   Input that holds the authentication information. For homogeneous session pools must be `NULL`.
+  /// - dbName, dbName_len:
+  ///   For session pooling, the name of the session pool returned by `OCISessionPoolCreate()` and its length.
+  /// - tagInfo, tagInfo_len:
+  ///   Only used for session pooling. Tag of the session that is requested and its length.
+  /// - retTagInfo, retTagInfo_len:
+  ///   Only used for session pooling. The tag of the returned session and its length.
+  /// - found:
+  ///   Only used for session pooling. If the type of session requested was found (that is, the complete `tagInfo`
+  ///   matches that of a session in the pool), this parameter is set to `TRUE`.
+  /// - mode:
+  ///   Mode of the call: `OCI_SESSGET_SPOOL`, `OCI_SESSGET_CPOOL`, `OCI_SESSGET_STMTCACHE` and others.
+  pub fn OCISessionGet(envhp: *mut OCIEnv,
+                       errhp: *mut OCIError,
+                       svchp: *mut *mut OCISvcCtx,// результат
+                       authInfop: *mut c_void,
+                       dbName: *const u8,
+                       dbName_len: u32,
+                       tagInfo: *const u8,
+                       tagInfo_len: u32,
+                       retTagInfo: *mut *mut u8,
+                       retTagInfo_len: *mut u32,
+                       found: *mut c_int,
+                       mode: u32) -> c_int;
+  /// Releases a session that was retrieved using `OCISessionGet()`.
+  ///
+  /// # Parameters
+  /// - svchp:
+  ///   The service context associated with the session or connection.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - tag, tag_len:
+  ///   Only used for session pooling. Tag of the session and its length. Ignored unless mode `OCI_SESSRLS_RETAG`
+  ///   is set.
+  /// - mode:
+  ///   `OCI_DEFAULT`, `OCI_SESSRLS_DROPSESS` (the session is dropped instead of returned to the pool) or
+  ///   `OCI_SESSRLS_RETAG`.
+  pub fn OCISessionRelease(svchp: *mut OCISvcCtx,
+                           errhp: *mut OCIError,
+                           tag: *const u8,
+                           tag_len: u32,
+                           mode: u32) -> c_int;
 }
\ No newline at end of file
@@ -364,7 +364,7 @@ impl<'conn, L: OCILobLocator> LobImpl<'conn, L> {
         &mut flag
       )
     };
-    try!(env.error().check(res));
+    try!(self.conn.error().check(res));
 
     Ok(flag != 0)
   }
@@ -11,7 +11,7 @@ use version::Version;
 use ffi::Handle;// Основные типобезопасные примитивы
 use ffi::{VersionHandle, InterruptHandle};// Типажи для безопасного моста к FFI
 
-use ffi::native::OCIError;// FFI типы
+use ffi::native::{OCIError, OCISvcCtx};// FFI типы
 
 /// Получает версию клиентской библиотеки. Для получения версии сервера необходимо [установить с ним соединение][1] и
 /// воспользоваться вызовом [`Connection::server_version()`][2].
@@ -124,6 +124,31 @@ pub fn reset<T: InterruptHandle>(hndl: &Handle<T>, err: &Handle<OCIError>) -> Db
   };
   err.check(res)
 }
+/// Проверяет, что соединение с сервером и сессия пользователя все еще живы, выполняя пустой запрос к серверу.
+///
+/// # Параметры
+/// - `hndl`:
+///   Хендл сервисного контекста соединения, которое требуется проверить.
+/// - `err`:
+///   Хендл для сбора ошибок, из которого будут извлечены подробности ошибки в случае, если она произойдет.
+///
+/// # OCI вызовы
+/// Функция вызывает [`OCIPing()`][1].
+///
+/// # Запросы к серверу (1)
+/// Функция выполняет один запрос к серверу при каждом вызове.
+///
+/// [1]: http://docs.oracle.com/database/122/LNOCI/miscellaneous-functions.htm
+pub fn ping(hndl: &Handle<OCISvcCtx>, err: &Handle<OCIError>) -> DbResult<()> {
+  let res = unsafe {
+    OCIPing(
+      hndl.native_mut(),
+      err.native_mut(),
+      0// Единственный допустимый режим -- OCI_DEFAULT
+    )
+  };
+  err.check(res)
+}
 // По странной прихоти разработчиков оракла на разных системах имя библиотеки разное
 #[cfg_attr(windows, link(name = "oci"))]
 #[cfg_attr(not(windows), link(name = "clntsh"))]
@@ -169,4 +194,11 @@ extern "C" {
   /// http://docs.oracle.com/database/122/LNOCI/miscellaneous-functions.htm#LNOCI17291
   fn OCIReset(hndlp: *mut c_void,
               errhp: *mut OCIError) -> c_int;
+
+  /// Makes a round-trip call to the server to confirm that the connection and the server are active.
+  ///
+  /// http://docs.oracle.com/database/122/LNOCI/miscellaneous-functions.htm
+  fn OCIPing(svchp: *mut OCISvcCtx,
+             errhp: *mut OCIError,
+             mode: u32) -> c_int;
 }
\ No newline at end of file
@@ -16,6 +16,7 @@ mod conn;
 mod hndl;
 mod misc;
 mod stmt;
+mod trans;
 pub mod lob;
 pub mod num;
 pub mod time;
@@ -30,6 +31,7 @@ pub use self::conn::*;
 pub use self::hndl::*;
 pub use self::misc::*;
 pub use self::stmt::*;
+pub use self::trans::*;
 
 
 #[derive(Debug)] pub enum OCIDescribe {} impl HandleType for OCIDescribe { const ID: Handle = Handle::Describe; }
@@ -44,7 +46,9 @@ impl InterruptHandle for OCIServer {}
 impl VersionHandle for OCISvcCtx {}
 impl InterruptHandle for OCISvcCtx {}
 #[derive(Debug)] pub enum OCISession {}  impl HandleType for OCISession  { const ID: Handle = Handle::Session; }
-#[derive(Debug)] pub enum OCIStmt {}
+#[derive(Debug)] pub enum OCICPool {}    impl HandleType for OCICPool    { const ID: Handle = Handle::CPool; }
+#[derive(Debug)] pub enum OCISPool {}    impl HandleType for OCISPool    { const ID: Handle = Handle::SPool; }
+#[derive(Debug)] pub enum OCIStmt {}     impl HandleType for OCIStmt     { const ID: Handle = Handle::Stmt; }
 impl AttrHandle  for OCIStmt { const ID: Handle = Handle::Stmt; }
 impl ParamHandle for OCIStmt { const ID: Handle = Handle::Stmt; }
 #[derive(Debug)] pub enum OCIBind {}     impl HandleType for OCIBind     { const ID: Handle = Handle::Bind; }
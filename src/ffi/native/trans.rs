@@ -0,0 +1,47 @@
+//! Функции, описанные в разделе [Transaction Functions][1] документации Oracle,
+//! посвященном управлению транзакциями.
+//!
+//! [1]: https://docs.oracle.com/database/122/LNOCI/transaction-functions.htm#LNOCI166
+
+use std::os::raw::c_int;
+
+use ffi::native::{OCIError, OCISvcCtx};// FFI типы
+
+// По странной прихоти разработчиков оракла на разных системах имя библиотеки разное
+#[cfg_attr(windows, link(name = "oci"))]
+#[cfg_attr(not(windows), link(name = "clntsh"))]
+extern "C" {
+  /// Commits the transaction associated with a specified service context.
+  ///
+  /// # Comments
+  /// The transaction currently associated with the service context is committed. If no transaction is associated
+  /// with the service context, then the call has no effect (a new transaction is not started).
+  ///
+  /// # Parameters
+  /// - svchp:
+  ///   The service context handle.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - flags:
+  ///   A flag used for one-phase commit optimization in global transactions. `OCI_DEFAULT` for local transactions.
+  pub fn OCITransCommit(svchp: *mut OCISvcCtx,
+                        errhp: *mut OCIError,
+                        flags: u32) -> c_int;
+  /// Rolls back the current transaction.
+  ///
+  /// # Comments
+  /// The current transaction, defined as the set of statements executed since the last `OCITransCommit()` or since
+  /// `OCISessionBegin()`, is rolled back. If the application is running in the object mode, then the modified or
+  /// updated objects in the object cache for this transaction are also rolled back.
+  ///
+  /// # Parameters
+  /// - svchp:
+  ///   A service context handle. The transaction currently set in the service context handle is rolled back.
+  /// - errhp:
+  ///   An error handle that you can pass to `OCIErrorGet()` for diagnostic information when there is an error.
+  /// - flags:
+  ///   You must pass a value of `OCI_DEFAULT` for this parameter.
+  pub fn OCITransRollback(svchp: *mut OCISvcCtx,
+                          errhp: *mut OCIError,
+                          flags: u32) -> c_int;
+}
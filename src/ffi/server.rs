@@ -2,11 +2,8 @@ use std::ptr;
 
 use {Environment, DbResult};
 use types::AttachMode;
-use version::Version;
-use ffi::native::server_version;
 
-use ffi::{Handle, Descriptor};// Основные типобезопасные примитивы
-use ffi::{HandleType, DescriptorType};// Типажи для безопасного моста к FFI
+use ffi::Handle;// Основные типобезопасные примитивы
 
 use ffi::native::{OCIServer, OCIError};// FFI типы
 use ffi::native::{OCIServerAttach, OCIServerDetach};// FFI функции
@@ -16,8 +13,8 @@ use ffi::native::{OCIServerAttach, OCIServerDetach};// FFI функции
 pub struct Server<'env> {
   env: &'env Environment<'env>,
   handle: Handle<OCIServer>,
-  /// Режим создания соединений, установленный при установлении соединения к серверу.
-  mode: AttachMode,
+  /// Признак того, что подключение к серверу еще не закрыто явно и должно быть закрыто при разрушении объекта.
+  attached: bool,
 }
 impl<'env> Server<'env> {
   /// Осуществляет подключение к указанному серверу в рамках данного окружения
@@ -35,23 +32,10 @@ impl<'env> Server<'env> {
       )
     };
     return match res {
-      0 => Ok(Server { env: env, handle: server, mode: mode }),
+      0 => Ok(Server { env: env, handle: server, attached: true }),
       e => Err(env.error.decode(e))
     };
   }
-  /// Получает окружение, создавшее данный сервер
-  #[inline]
-  pub fn get_env(&self) -> &'env Environment<'env> {
-    self.env
-  }
-  #[inline]
-  pub fn new_handle<T: HandleType>(&self) -> DbResult<Handle<T>> {
-    self.env.new_handle()
-  }
-  #[inline]
-  pub fn new_descriptor<T: DescriptorType>(&self) -> DbResult<Descriptor<T>> {
-    self.env.new_descriptor()
-  }
   /// Получает хендл для записи ошибок во время общения с базой данных. Хендл берется из окружения, которое породило
   /// данный сервер. В случае возникновения ошибки при вызове FFI-функции она может быть получена из хендла с помощью
   /// вызова `decode(ffi_result)`.
@@ -63,23 +47,28 @@ impl<'env> Server<'env> {
   pub fn handle(&self) -> &Handle<OCIServer> {
     &self.handle
   }
-  /// Возвращает версию сервера Oracle-а, к которому подключен клиент.
-  ///
-  /// # Запросы к серверу (1)
-  /// Функция выполняет один запрос к серверу при каждом вызове.
-  pub fn version(&self) -> DbResult<Version> {
-    server_version(&self.handle, self.error())
+  /// Отключается от сервера, возвращая ошибку, если отключиться не удалось. В отличие от разрушения объекта,
+  /// которое игнорирует ошибки, позволяет узнать о них.
+  pub fn close(mut self) -> DbResult<()> {
+    self.attached = false;
+    self.detach()
   }
-}
-impl<'env> Drop for Server<'env> {
-  fn drop(&mut self) {
+  fn detach(&self) -> DbResult<()> {
     let res = unsafe {
       OCIServerDetach(
         self.handle.native_mut(),
         self.error().native_mut(),
-        self.mode as u32
+        0// Единственный допустимый режим -- OCI_DEFAULT, в том числе и для подключений через пул соединений
       )
     };
-    self.error().check(res).expect("OCIServerDetach");
+    self.error().check(res)
+  }
+}
+impl<'env> Drop for Server<'env> {
+  fn drop(&mut self) {
+    if self.attached {
+      // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+      let _ = self.detach();
+    }
   }
 }
\ No newline at end of file
@@ -177,6 +177,11 @@ pub enum Attr {
   /// Количество строк, извлеченных последним последним вызовом `OCIStmtFetch2` (для `select` выражений)
   /// или количество затронутых строк (для `update`, `insert` и `delete` выражений).
   RowCount = 9,
+  // Количество строк, которые сервер вернет вместе с результатом выполнения или извлечения, не дожидаясь
+  // явного запроса на их получение. Имеет то же значение, что и `Position`, поэтому используется он.
+  //PrefetchRows = 11,
+  /// Объем памяти в байтах, выделяемый под строки, получаемые заранее, не дожидаясь явного запроса на их получение.
+  PrefetchMemory = 13,
   /// Атрибут на хендле выражения, показывает количество колонок, извлекаемых `select` выражением
   ParamCount = 18,
   Username = 22,
@@ -185,10 +190,66 @@ pub enum Attr {
   StmtType = 24,
   /// Количество строк, извлеченных в последний вызов `OCIStmtFetch2` или `OCIExecute`.
   RowFetched = 197,
+  /// Номер строки (нумерация с 1), на которой находится прокручиваемый курсор после последнего извлечения.
+  CurrentPosition = 164,
+  /// Смещение в тексте выражения (нумерация с 0), в котором сервер обнаружил ошибку при разборе выражения.
+  ParseErrorOffset = 129,
+  /// Текст выражения, переданный при его подготовке.
+  Statement = 144,
+  /// Количество выражений, хранимых в кеше выражений сервисного контекста. Установка ненулевого значения включает
+  /// кеширование выражений, нулевого -- выключает.
+  StmtCacheSize = 176,
+  /// Состояние соединения с сервером, известное клиенту: `0` -- соединение потеряно, `1` -- соединение в порядке.
+  /// Получение атрибута не требует обращения к серверу.
+  ServerStatus = 143,
   /// Количество строк, извлеченных последним последним вызовом `OCIStmtFetch2` (для `select` выражений)
   /// или количество затронутых строк (для `update`, `insert` и `delete` выражений). Значение данного атрибута
   /// представлено в виде `u64` числа, а не `u32`, как `RowCount`, но он появился только с версии 12.1.
   RowCount2 = 457,
+  /// Количество ошибок, возникших при массивном выполнении выражения в режиме `OCI_BATCH_ERRORS`.
+  NumDmlErrors = 73,
+  /// Номер итерации (нумерация с 0), на которой возникла ошибка, полученная из хендла ошибки в режиме `OCI_BATCH_ERRORS`.
+  DmlRowOffset = 74,
+  /// Массив количеств строк, затронутых каждой итерацией массивного выполнения выражения в режиме
+  /// `OCI_RETURN_ROW_COUNT_ARRAY`.
+  DmlRowCountArray = 469,
+  /// Атрибут на хендле связывания, показывает количество строк, значения которых сервер вернет в переменную из
+  /// предложения `RETURNING ... INTO` на текущей итерации выполнения выражения.
+  RowsReturned = 42,
+
+// Attributes for connection pools
+  /// Если установлен, то при отсутствии свободных соединений в пуле ошибка возвращается немедленно.
+  ConnNoWait        = 146,
+  /// Количество соединений пула, используемых в данный момент.
+  ConnBusyCount     = 147,
+  /// Количество соединений пула, открытых в данный момент.
+  ConnOpenCount     = 148,
+  /// Время в секундах, после которого простаивающие соединения пула закрываются.
+  ConnTimeout       = 149,
+  /// Минимальное количество соединений в пуле.
+  ConnMin           = 151,
+  /// Максимальное количество соединений в пуле.
+  ConnMax           = 152,
+  /// Количество соединений, на которое увеличивается пул, когда свободных соединений не осталось.
+  ConnIncr          = 153,
+
+// Attributes for session pools
+  /// Время в секундах, после которого простаивающие сессии пула закрываются.
+  SPoolTimeout      = 308,
+  /// Поведение пула при запросе сессии, когда все сессии заняты и пул достиг максимального размера.
+  SPoolGetMode      = 309,
+  /// Количество сессий пула, используемых в данный момент.
+  SPoolBusyCount    = 310,
+  /// Количество сессий пула, открытых в данный момент.
+  SPoolOpenCount    = 311,
+  /// Минимальное количество сессий в пуле.
+  SPoolMin          = 312,
+  /// Максимальное количество сессий в пуле.
+  SPoolMax          = 313,
+  /// Количество сессий, на которое увеличивается пул, когда свободных сессий не осталось.
+  SPoolIncr         = 314,
+  /// Время ожидания свободной сессии в миллисекундах в режиме `OCI_SPOOL_ATTRVAL_TIMEDWAIT`. Доступно с версии 12.2.
+  SPoolWaitTimeout  = 506,
 
 
 // Attributes common to Columns and Stored Procs
@@ -264,6 +325,17 @@ pub enum CachingMode {
 impl Default for CachingMode {
   fn default() -> Self { CachingMode::Default }
 }
+/// Режим освобождения выражения при вызове `OCIStmtRelease()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReleaseMode {
+  /// Если кеширование выражений включено, выражение помещается в кеш для повторного использования.
+  Default     = 0,
+  /// Выражение удаляется из кеша выражений и не может быть повторно использовано.
+  CacheDelete = 0x0010,
+}
+impl Default for ReleaseMode {
+  fn default() -> Self { ReleaseMode::Default }
+}
 /// Коды ошибок, которые могут вернуть функции оракла (не путать с кодами ошибок оракла `ORA-xxxxx`)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -403,6 +475,44 @@ impl Default for BindMode {
   fn default() -> Self { BindMode::Default }
 }
 
+/// Режим создания пула сессий при вызове `OCISessionPoolCreate()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum SessionPoolMode {
+  /// Пул создается с параметрами по умолчанию.
+  Default       = 0,
+  /// Изменяет параметры уже созданного пула (`sessMin`, `sessMax` и `sessIncr`).
+  Reinitialize  = 1 << 0,
+  /// Все сессии пула создаются с одними и теми же учетными данными, указанными при создании пула.
+  Homogeneous   = 1 << 1,
+  /// Включает кеширование выражений для сессий пула.
+  StmtCache     = 1 << 2,
+}
+/// Режим получения сессии при вызове `OCISessionGet()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum SessionGetMode {
+  /// Сессия получается из пула сессий.
+  SPool         = 1 << 0,
+  /// Для сессии включается кеширование выражений.
+  StmtCache     = 1 << 2,
+  /// Сессия получается из пула соединений.
+  CPool         = 1 << 9,
+}
+/// Поведение пула сессий при запросе сессии, когда все сессии заняты и пул достиг максимального размера
+/// (значение атрибута `OCI_ATTR_SPOOL_GETMODE`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum SessionPoolGetMode {
+  /// Ожидать освобождения сессии неограниченно долго.
+  Wait      = 0,
+  /// Немедленно вернуть ошибку.
+  NoWait    = 1,
+  /// Создать новую сессию сверх максимального количества.
+  ForceGet  = 2,
+  /// Ожидать освобождения сессии не дольше времени, заданного атрибутом `OCI_ATTR_SPOOL_WAIT_TIMEOUT`.
+  TimedWait = 3,
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[allow(dead_code)]
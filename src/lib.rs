@@ -14,7 +14,8 @@
 //!   // Инициализируем клиентскую библиотеку Oracle
 //!   let env = Environment::new(InitParams::default()).expect("Can't create ORACLE environment");
 //! 
-//!   // Создаем параметры. Вскоре их можно будет распарсить из строки (jdbc и sql*plus версий)
+//!   // Создаем параметры. Их также можно распарсить из строки (jdbc и sql*plus версий):
+//!   // `let params: ConnectParams = "username/password@localhost:1521/XE".parse().unwrap();`
 //!   let params = ConnectParams {
 //!     dblink: "".into(),
 //!     attach_mode: AttachMode::default(),
@@ -44,11 +45,19 @@
 extern crate num_integer;
 extern crate num_traits;
 
+use std::cell::{Cell, RefCell};
+use std::ptr;
+use std::sync::Mutex;
+
+pub mod cancel;
 pub mod convert;
 pub mod error;
 pub mod lob;
 pub mod params;
+pub mod pool;
+pub mod reconnect;
 pub mod stmt;
+pub mod transaction;
 pub mod types;
 pub mod version;
 mod ffi;
@@ -65,33 +74,45 @@ pub type Result<T> = std::result::Result<T, error::Error>;
 /// через FFI интерфейс.
 type DbResult<T> = std::result::Result<T, error::DbError>;
 
+use cancel::CancelHandle;
+use error::{DbError, Diagnostic, Error};
 use params::{InitParams, ConnectParams, Credentials};
 use stmt::Statement;
-use types::{AuthMode, Syntax};
-use version::Version;
+use transaction::Transaction;
+use types::{AuthMode, CreateMode, Syntax};
+use version::{client_version, Version};
 
 use ffi::{Env, Server, Handle, Descriptor};// Основные типобезопасные примитивы
 use ffi::{HandleType, DescriptorType};// Типажи для безопасного моста к FFI
 
-use ffi::types::{Attr, CredentialMode};
-use ffi::native::{OCIEnv, OCISvcCtx, OCISession, OCIError};// FFI типы
-use ffi::native::{OCISessionBegin, OCISessionEnd};// FFI функции
+use ffi::types::{Attr, CachingMode, CredentialMode};
+use ffi::native::{OCIEnv, OCIServer, OCISvcCtx, OCISession, OCIError};// FFI типы
+use ffi::native::{OCISessionBegin, OCISessionEnd, OCISessionRelease, OCITransCommit, OCITransRollback};// FFI функции
+use ffi::native::{ping, server_version};
 use ffi::native::time::{get_time_offset, sys_timestamp, TimestampWithTZ};
 
 // Для того, чтобы пользоваться функциями типажей, они должны быть в области видимости
 use ffi::attr::AttrHolder;
 
+/// Количество строк, извлекаемых из буфера `DBMS_OUTPUT` за один запрос к серверу.
+const OUTPUT_LINES: usize = 32;
+
 //-------------------------------------------------------------------------------------------------
 /// Окружение представляет собой менеджер соединений к базе. При разрушении окружения
 /// все открытые соединения автоматически закрываются а незавершенные транзакции в них
 /// откатываются.
 #[derive(Debug)]
 pub struct Environment<'e> {
-  /// Автоматически закрываемый враппер над низкоуровневыми функциями работы с окружением Oracle
-  env: Env<'e>,
+  /// Объекты отмены запросов, созданные для соединений окружения. Объявлены первыми, т.к. должны быть отсоединены
+  /// до освобождения хендла окружения.
+  cancels: CancelHandles,
   /// Хендл для приема ошибок от нативных вызовов оракла. Позволяет затем получить код ошибки
   /// и ее описание.
+  ///
+  /// Объявлен раньше окружения, т.к. должен быть освобожден до освобождения хендла окружения.
   error: Handle<OCIError>,
+  /// Автоматически закрываемый враппер над низкоуровневыми функциями работы с окружением Oracle
+  env: Env<'e>,
 }
 impl<'e> Environment<'e> {
   /// Создает окружение -- менеджер подключений к базе данных. Поле `mode` в параметрах позволяет задать возможности,
@@ -99,18 +120,42 @@ impl<'e> Environment<'e> {
   ///
   /// # OCI вызовы
   /// Осуществляет OCI вызов [`OCIEnvNlsCreate()`][new]. При разрушении объекта будет осуществлен OCI вызов
-  /// [`OCITerminate()`][end].
+  /// [`OCIHandleFree()`][end].
   ///
   /// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#GUID-0B6911A9-4B46-476C-BC5E-B87581666CD9
-  /// [end]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#GUID-B7BC5F9E-811C-490A-B308-472A12D690D2
+  /// [end]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm
   pub fn new<P: Into<InitParams>>(params: P) -> Result<Self> {
-    let mut env = try!(Env::new(params.into()));
+    let env = try!(Env::new(params.into()));
     let err: Handle<OCIError> = try!(env.new_error_handle());
 
-    Ok(Environment { env: env, error: err })
+    Ok(Environment { cancels: CancelHandles::default(), error: err, env: env })
+  }
+  /// Освобождает хендл для приема ошибок и хендл окружения, возвращая первую из возникших при этом ошибок.
+  ///
+  /// В отличие от разрушения объекта окружения, которое игнорирует любые ошибки освобождения, позволяет узнать о них.
+  /// Все соединения, созданные окружением, должны быть закрыты до вызова -- за этим следит компилятор.
+  ///
+  /// # OCI вызовы
+  /// Осуществляет OCI вызовы [`OCIHandleFree()`][free] для хендла ошибок и хендла окружения.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [free]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm
+  pub fn close(self) -> Result<()> {
+    let Environment { cancels, error, env } = self;
+    drop(cancels);
+    let error = error.free();
+    let env = env.close();
+    error.and(env).map_err(Into::into)
   }
   /// Осуществляет подключение к базе данных с указанными параметрами.
   ///
+  /// Если в параметрах указан режим [`AttachMode::CPool`][cpool], то в качестве адреса базы должно быть указано
+  /// [имя][name] предварительно созданного [пула соединений][pool] -- в этом случае сессия будет мультиплексирована
+  /// через одно из его физических соединений. Пул должен жить дольше полученного соединения, поэтому для подключения
+  /// через пул предпочтительнее использовать метод [`ConnectionPool::connect()`][connect], который это гарантирует.
+  ///
   /// # OCI вызовы
   /// Осуществляет OCI вызов [`OCISessionBegin()`][new]. При разрушении объекта соединения будет осуществлен OCI вызов
   /// [`OCISessionEnd()`][end].
@@ -121,6 +166,11 @@ impl<'e> Environment<'e> {
   ///
   /// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#GUID-31B1FDB3-056E-4AF9-9B89-8DA6AA156947
   /// [end]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#LNOCI17123
+  ///
+  /// [cpool]: ./types/enum.AttachMode.html#variant.CPool
+  /// [name]: ./pool/struct.ConnectionPool.html#method.name
+  /// [pool]: ./pool/struct.ConnectionPool.html
+  /// [connect]: ./pool/struct.ConnectionPool.html#method.connect
   #[inline]
   pub fn connect<P: Into<ConnectParams>>(&'e self, params: P) -> Result<Connection<'e>> {
     Connection::new(&self, &params.into()).map_err(Into::into)
@@ -131,12 +181,6 @@ impl<'e> Environment<'e> {
   fn new_handle<T: HandleType>(&self) -> DbResult<Handle<T>> {
     self.env.new_handle(self.error.native_mut())
   }
-  /// Создает новый дескриптор для хранения объектов указанного типа. Дескриптор будет автоматически закрыт при выходе из зоны
-  /// видимости переменной, хранящей его.
-  #[inline]
-  fn new_descriptor<T: DescriptorType>(&self) -> DbResult<Descriptor<T>> {
-    Descriptor::new(&self)
-  }
   /// Получает хендл для записи ошибок во время общения с базой данных. В случае возникновения ошибки при вызове
   /// FFI-функции она может быть получена из хендла с помощью вызова `decode(ffi_result)`.
   #[inline]
@@ -172,10 +216,15 @@ impl<'e> Default for Environment<'e> {
 /// [end2]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#LNOCI17123
 #[derive(Debug)]
 pub struct Connection<'e> {
+  /// Окружение, в рамках которого создано соединение.
+  env: &'e Environment<'e>,
   /// Хендл сервера, к которому будут направляться запросы. Несколько пользователей (подключений)
   /// могут одновременно работать с одним сервером через общий хендл. В настоящий момент это не
   /// поддерживается, каждое подключение использует свое сетевое соединение к серверу.
-  server: Server<'e>,
+  ///
+  /// Для соединений, полученных из пула, отсутствует -- сетевым соединением управляет пул.
+  /// Хранится только для того, чтобы отключиться от сервера при закрытии соединения.
+  server: Option<Server<'e>>,
   /// Хендл, хранящий информацию об учетных данных пользователя, независимо от того, к какой инстанции БД он
   /// подключен и подключен ли вообще.
   context: Handle<OCISvcCtx>,
@@ -183,13 +232,41 @@ pub struct Connection<'e> {
   session: Handle<OCISession>,
   /// Режим аутетификации, который использовался при создании соединения. Необходим при закрытии
   auth_mode: AuthMode,
+  /// Признак того, что соединение получено из пула и при закрытии должно быть возвращено в него.
+  pooled: bool,
+  /// Собственный хендл для приема ошибок соединения, полученного из [пула сессий][1]. Такие соединения могут работать
+  /// одновременно в разных потоках, поэтому не пользуются общим хендлом ошибок окружения. У остальных соединений
+  /// отсутствует.
+  ///
+  /// [1]: ./pool/struct.SessionPool.html
+  error: Option<Handle<OCIError>>,
+  /// Признак того, что соединение уже было явно закрыто вызовом [`close()`][1] и при разрушении его закрывать не нужно.
+  ///
+  /// [1]: #method.close
+  closed: bool,
+  /// Объект для отмены запросов из другого потока, копии которого выдаются методом [`cancel_handle()`][1]. Создается
+  /// при первом вызове этого метода, т.к. требует отдельного хендла ошибок. При закрытии соединения отсоединяется от него.
+  ///
+  /// [1]: #method.cancel_handle
+  cancel: RefCell<Option<CancelHandle>>,
+  /// Количество строк, получаемых заранее, устанавливаемое всем выражениям, подготовленным данным соединением.
+  prefetch_rows: Cell<Option<u32>>,
+  /// Объем памяти под строки, получаемые заранее, устанавливаемый всем выражениям, подготовленным данным соединением.
+  prefetch_memory: Cell<Option<u32>>,
+  /// Признак того, что для соединения существует объект [транзакции][1]. Пока он существует, создать еще один или
+  /// зафиксировать/откатить транзакцию методами самого соединения нельзя.
+  ///
+  /// [1]: ./transaction/struct.Transaction.html
+  transaction: Cell<bool>,
+  /// Версия сервера, полученная при первой проверке того, поддерживает ли он возможность, доступную не во всех
+  /// версиях. Запоминается, чтобы не выполнять запрос к серверу при каждой проверке.
+  version: Cell<Option<Version>>,
 }
 impl<'e> Connection<'e> {
   fn new(env: &'e Environment, params: &ConnectParams) -> DbResult<Self> {
     let server = try!(Server::new(env, Some(&params.dblink), params.attach_mode));
     let mut context: Handle<OCISvcCtx > = try!(env.new_handle());
     let mut session: Handle<OCISession> = try!(env.new_handle());
-
     let credMode = match params.credentials {
       Credentials::Rdbms { ref username, ref password } => {
         // Ассоциируем имя пользователя и пароль с сессией.
@@ -219,14 +296,66 @@ impl<'e> Connection<'e> {
     try!(env.error.check(res));
     try!(context.set_handle(&session, Attr::Session, &env.error));
 
-    Ok(Connection { server: server, context: context, session: session, auth_mode: params.auth_mode })
+    Ok(Connection {
+      env: env,
+      server: Some(server),
+      context: context,
+      session: session,
+      auth_mode: params.auth_mode,
+      pooled: false,
+      error: None,
+      closed: false,
+      cancel: RefCell::new(None),
+      prefetch_rows: Cell::new(None),
+      prefetch_memory: Cell::new(None),
+      transaction: Cell::new(false),
+      version: Cell::new(None),
+    })
+  }
+  /// Создает соединение для сессии, полученной из пула вызовом `OCISessionGet()`. Хендлами контекста и сессии
+  /// владеет пул, поэтому они не освобождаются при разрушении соединения, а сама сессия возвращается в пул.
+  /// Хендл ошибок, которым пользовались при получении сессии, становится собственным хендлом ошибок соединения.
+  fn from_pool(env: &'e Environment, context: *mut OCISvcCtx, error: Handle<OCIError>) -> DbResult<Self> {
+    let context: Handle<OCISvcCtx> = Handle::borrowed(context);
+    let session: *mut OCISession = try!(context.get_handle(Attr::Session, &error));
+
+    Ok(Connection {
+      env: env,
+      server: None,
+      context: context,
+      session: Handle::borrowed(session),
+      auth_mode: AuthMode::default(),
+      pooled: true,
+      error: Some(error),
+      closed: false,
+      cancel: RefCell::new(None),
+      prefetch_rows: Cell::new(None),
+      prefetch_memory: Cell::new(None),
+      transaction: Cell::new(false),
+      version: Cell::new(None),
+    })
   }
   /// Получает хендл для записи ошибок во время общения с базой данных. Хендл берется из окружения, которое породило
-  /// данное соединение. В случае возникновения ошибки при вызове FFI-функции она может быть получена из хендла с помощью
-  /// вызова `decode(ffi_result)`.
+  /// данное соединение, если у соединения нет собственного хендла. В случае возникновения ошибки при вызове FFI-функции
+  /// она может быть получена из хендла с помощью вызова `decode(ffi_result)`.
   #[inline]
   fn error(&self) -> &Handle<OCIError> {
-    self.server.error()
+    self.error.as_ref().unwrap_or(self.env.error())
+  }
+  /// Создает новый хендл для хранения объектов указанного типа. В отличие от одноименного метода окружения,
+  /// ошибка создания записывается в хендл ошибок соединения, поэтому соединения, полученные из пула сессий, могут
+  /// создавать хендлы одновременно из разных потоков.
+  #[inline]
+  fn new_handle<T: HandleType>(&self) -> DbResult<Handle<T>> {
+    self.env.env.new_handle(self.error().native_mut())
+  }
+  /// Создает новый дескриптор для хранения объектов указанного типа. Ошибка создания записывается в хендл ошибок
+  /// соединения, как и в методе [`new_handle()`][1].
+  ///
+  /// [1]: #method.new_handle
+  #[inline]
+  fn new_descriptor<T: DescriptorType>(&self) -> DbResult<Descriptor<'e, T>> {
+    Descriptor::new(self.env, self.error())
   }
   #[inline]
   unsafe fn as_descriptor<T: DescriptorType>(&self, raw: &[u8]) -> &T {
@@ -237,7 +366,7 @@ impl<'e> Connection<'e> {
   /// Получает окружение, которое создало данное соединение.
   #[inline]
   pub fn get_env(&self) -> &'e Environment<'e> {
-    self.server.get_env()
+    self.env
   }
   /// Возвращает версию сервера Oracle-а, к которому подключен клиент.
   ///
@@ -252,7 +381,91 @@ impl<'e> Connection<'e> {
   /// [1]: ./fn.client_version.html
   /// [2]: http://docs.oracle.com/database/122/LNOCI/miscellaneous-functions.htm#LNOCI17293
   pub fn server_version(&self) -> Result<Version> {
-    self.server.version().map_err(Into::into)
+    server_version(&self.context, self.error()).map_err(Into::into)
+  }
+  /// Проверяет, что и клиентская библиотека, и сервер имеют версию не ниже указанной. Версия сервера запрашивается
+  /// только при первой проверке.
+  fn supports(&self, version: Version) -> Result<bool> {
+    if client_version() < version {
+      return Ok(false);
+    }
+    let server = match self.version.get() {
+      Some(v) => v,
+      None => {
+        let v = try!(self.server_version());
+        self.version.set(Some(v));
+        v
+      },
+    };
+    Ok(server >= version)
+  }
+  /// Проверяет, что сетевое соединение с сервером и сессия пользователя все еще живы. Если соединение было разорвано
+  /// (например, межсетевым экраном), функция вернет ошибку, для которой [`Error::is_connection_lost()`][1] вернет `true`.
+  ///
+  /// В отличие от [`is_healthy()`][2] действительно обращается к серверу, поэтому обнаруживает и те разрывы соединения,
+  /// о которых клиент еще не знает.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCIPing()`][3].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу при каждом вызове.
+  ///
+  /// [1]: ./error/enum.Error.html#method.is_connection_lost
+  /// [2]: #method.is_healthy
+  /// [3]: http://docs.oracle.com/database/122/LNOCI/miscellaneous-functions.htm
+  pub fn ping(&self) -> Result<()> {
+    ping(&self.context, self.error()).map_err(Into::into)
+  }
+  /// Проверяет, считает ли клиент соединение с сервером живым. Клиент узнает о разрыве соединения только при очередном
+  /// обращении к серверу, поэтому `true` не гарантирует, что следующий запрос будет успешным -- для такой проверки
+  /// используйте [`ping()`][1]. Зато `false` однозначно означает, что соединение потеряно и его нужно установить заново.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызовы [`OCIAttrGet()`][2] для получения хендла сервера из сервисного контекста и его атрибута
+  /// `OCI_ATTR_SERVER_STATUS`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.ping
+  /// [2]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  pub fn is_healthy(&self) -> Result<bool> {
+    let server: *mut OCIServer = try!(self.context.get_handle(Attr::Server, self.error()));
+    let server: Handle<OCIServer> = Handle::borrowed(server);
+    let status: u32 = try!(server.get_(Attr::ServerStatus, self.error()));
+    // OCI_SERVER_NORMAL
+    Ok(status == 1)
+  }
+  /// Получает объект, позволяющий прервать запрос, выполняющийся в данном соединении, из другого потока. Прерванное
+  /// выполнение выражения или извлечение строк вернет ошибку [`Error::Cancelled`][1], после чего соединением можно
+  /// продолжать пользоваться.
+  ///
+  /// Объект можно передать в другой поток, и он может пережить соединение и даже окружение -- после закрытия
+  /// соединения или разрушения окружения отмена через него ничего не делает. Окружение, в котором создано соединение,
+  /// должно быть создано в режиме [`CreateMode::Threaded`][2], в противном случае возвращается ошибка
+  /// [`Error::NotThreaded`][3].
+  ///
+  /// Объект создается при первом вызове, последующие вызовы возвращают его копии.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ./error/enum.Error.html#variant.Cancelled
+  /// [2]: ./types/enum.CreateMode.html#variant.Threaded
+  /// [3]: ./error/enum.Error.html#variant.NotThreaded
+  pub fn cancel_handle(&self) -> Result<CancelHandle> {
+    if self.env.env.params().mode != CreateMode::Threaded {
+      return Err(Error::NotThreaded);
+    }
+    let mut cancel = self.cancel.borrow_mut();
+    if let Some(ref handle) = *cancel {
+      return Ok(handle.clone());
+    }
+    let handle = CancelHandle::new(self.context.native_mut(), try!(self.new_handle()));
+    self.env.cancels.register(&handle);
+    *cancel = Some(handle.clone());
+    Ok(handle)
   }
   /// Осуществляет разбор SQL-выражения и создает подготовленное выражение для дальнейшего эффективного исполнения запросов.
   /// Выражение использует родной для сервера базы данных синтаксис разбора запросов. Если вам требуется использовать конкретный
@@ -304,7 +517,133 @@ impl<'e> Connection<'e> {
   /// [1]: #method.prepare
   #[inline]
   pub fn prepare_with_syntax(&'e self, syntax: Syntax, sql: &str) -> Result<Statement<'e, 'e>> {
-    Statement::new(&self, sql, None, syntax).map_err(Into::into)
+    Statement::new(&self, sql, None, syntax, CachingMode::Default).map_err(Into::into)
+  }
+  /// Осуществляет разбор SQL-выражения и создает подготовленное выражение, используя кеш выражений соединения. Текст
+  /// выражения используется в качестве ключа кеша: если выражение с таким текстом уже было подготовлено этим методом
+  /// и закрыто, оно будет взято из кеша без повторного разбора. При закрытии выражение помещается обратно в кеш.
+  ///
+  /// Кеш выражений должен быть включен методом [`set_statement_cache_size()`][1], в противном случае метод работает
+  /// так же, как и [`prepare()`][2].
+  ///
+  /// Кешированное выражение сохраняет связанные с ним переменные, поэтому выражение, с которым на момент закрытия
+  /// связаны буферы (любым способом, кроме [`execute_with()`][3] и [`query_with()`][4], отвязывающих параметры после
+  /// выполнения), удаляется из кеша, чтобы следующее извлечение из кеша не ссылалось на освобожденную память.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// conn.set_statement_cache_size(20).unwrap();
+  /// for i in 0..3 {
+  ///   // Разбор выражения выполняется только на первой итерации
+  ///   let mut stmt = conn.prepare_cached("select :1 from dual").unwrap();
+  ///   stmt.query_with(&[&i]).unwrap();
+  /// }
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Объект выражения создается или извлекается из кеша OCI вызовом [`OCIStmtPrepare2()`][new]. При разрушении объекта
+  /// выражения будет осуществлен OCI вызов [`OCIStmtRelease()`][end], возвращающий выражение в кеш.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [new]: http://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17168
+  /// [end]: http://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17170
+  ///
+  /// [1]: #method.set_statement_cache_size
+  /// [2]: #method.prepare
+  /// [3]: ./stmt/struct.Statement.html#method.execute_with
+  /// [4]: ./stmt/struct.Statement.html#method.query_with
+  #[inline]
+  pub fn prepare_cached<'k>(&'e self, sql: &'k str) -> Result<Statement<'e, 'k>> {
+    self.prepare_with_key(sql, sql)
+  }
+  /// Осуществляет разбор SQL-выражения и создает подготовленное выражение, используя кеш выражений соединения с
+  /// указанным ключом. Если выражение с таким ключом есть в кеше, оно будет взято из кеша без повторного разбора, а
+  /// текст `sql` не используется. При закрытии выражение помещается обратно в кеш под этим ключом.
+  ///
+  /// Кеш выражений должен быть включен методом [`set_statement_cache_size()`][1], в противном случае метод работает
+  /// так же, как и [`prepare()`][2].
+  ///
+  /// Кешированное выражение сохраняет связанные с ним переменные, поэтому выражение, с которым на момент закрытия
+  /// связаны буферы (любым способом, кроме [`execute_with()`][3] и [`query_with()`][4], отвязывающих параметры после
+  /// выполнения), удаляется из кеша, чтобы следующее извлечение из кеша не ссылалось на освобожденную память.
+  ///
+  /// # OCI вызовы
+  /// Объект выражения создается или извлекается из кеша OCI вызовом [`OCIStmtPrepare2()`][new]. При разрушении объекта
+  /// выражения будет осуществлен OCI вызов [`OCIStmtRelease()`][end], возвращающий выражение в кеш.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [new]: http://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17168
+  /// [end]: http://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17170
+  ///
+  /// [1]: #method.set_statement_cache_size
+  /// [2]: #method.prepare
+  /// [3]: ./stmt/struct.Statement.html#method.execute_with
+  /// [4]: ./stmt/struct.Statement.html#method.query_with
+  #[inline]
+  pub fn prepare_with_key<'k>(&'e self, key: &'k str, sql: &str) -> Result<Statement<'e, 'k>> {
+    Statement::new(&self, sql, Some(key), Syntax::default(), CachingMode::Default).map_err(Into::into)
+  }
+  /// Удаляет из кеша выражений соединения выражение с указанным ключом. Возвращает `false`, если выражения с таким
+  /// ключом в кеше не было. Для выражений, подготовленных методом [`prepare_cached()`][1], ключом является текст
+  /// выражения.
+  ///
+  /// # OCI вызовы
+  /// Выражение ищется в кеше OCI вызовом [`OCIStmtPrepare2()`][new] и удаляется из него OCI вызовом
+  /// [`OCIStmtRelease()`][end].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [new]: http://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17168
+  /// [end]: http://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17170
+  ///
+  /// [1]: #method.prepare_cached
+  pub fn evict_cached(&'e self, key: &str) -> Result<bool> {
+    match Statement::new(&self, "", Some(key), Syntax::default(), CachingMode::CacheSearchOnly) {
+      Ok(stmt) => { stmt.evict(); Ok(true) },
+      // ORA-24431: Statement does not exist in the cache
+      Err(DbError::Fault(error::Info { code: 24431, .. })) => Ok(false),
+      Err(e) => Err(e.into()),
+    }
+  }
+  /// Получает максимальное количество выражений, хранимых в кеше выражений соединения. `0` означает, что кеширование
+  /// выражений выключено.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCIAttrGet()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  pub fn statement_cache_size(&self) -> Result<u32> {
+    self.context.get_(Attr::StmtCacheSize, self.error()).map_err(Into::into)
+  }
+  /// Устанавливает максимальное количество выражений, хранимых в кеше выражений соединения. Ненулевое значение включает
+  /// кеширование выражений, подготавливаемых методами [`prepare_cached()`][1] и [`prepare_with_key()`][2], `0` --
+  /// выключает его, очищая кеш.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCIAttrSet()`][3].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.prepare_cached
+  /// [2]: #method.prepare_with_key
+  /// [3]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17131
+  pub fn set_statement_cache_size(&self, size: u32) -> Result<()> {
+    // Установка атрибута требует изменяемого хендла, поэтому используем невладеющую обертку над тем же контекстом
+    let mut context: Handle<OCISvcCtx> = Handle::borrowed(self.context.native_mut());
+    context.set_(size, Attr::StmtCacheSize, self.error()).map_err(Into::into)
   }
   /// Получает текущий часовой пояс сессии в виде пары чисел, означающих смещение в часах и минутах.
   /// Диапазон возможных значений результата: от `-12:59` до `+14:00`.
@@ -332,24 +671,296 @@ impl<'e> Connection<'e> {
   /// [new]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17132
   /// [end]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17134
   pub fn get_current_time_offset(&self) -> Result<(i8, i8)> {
-    let mut d: Descriptor<TimestampWithTZ> = try!(self.server.new_descriptor());
+    let mut d: Descriptor<TimestampWithTZ> = try!(self.new_descriptor());
     // Получаем текущее время сервера. Так как оно возвращается в виде временной метки с часовым поясом,
     // а часовой пояс зависит от часового пояса сессии, то таким образом мы можем получить часовой пояс сессии.
     try!(sys_timestamp(&self.session, self.error(), d.native_mut()));
     get_time_offset(&self.session, self.error(), d.as_ref()).map_err(Into::into)
   }
-}
-impl<'e> Drop for Connection<'e> {
-  fn drop(&mut self) {
+  /// Фиксирует текущую транзакцию соединения. Если транзакции нет (никаких изменений не производилось), то вызов
+  /// ничего не делает.
+  ///
+  /// Пока для соединения существует объект [транзакции][2], возвращает ошибку [`TransactionInProgress`][3] --
+  /// транзакцию следует зафиксировать через него.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCITransCommit()`][1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу при каждом вызове.
+  ///
+  /// [1]: http://docs.oracle.com/database/122/LNOCI/transaction-functions.htm#LNOCI17197
+  /// [2]: ./transaction/struct.Transaction.html
+  /// [3]: ./error/enum.Error.html#variant.TransactionInProgress
+  pub fn commit(&self) -> Result<()> {
+    try!(self.check_no_transaction());
+    self.commit_impl().map_err(Into::into)
+  }
+  /// Фиксирует текущую транзакцию соединения, не проверяя наличие объекта транзакции.
+  fn commit_impl(&self) -> DbResult<()> {
     let res = unsafe {
-      OCISessionEnd(
+      OCITransCommit(
         self.context.native_mut(),
         self.error().native_mut(),
-        self.session.native_mut(),
-        self.auth_mode as u32
+        0// Режим OCI_DEFAULT, локальная транзакция
       )
     };
-    self.error().check(res).expect("OCISessionEnd");
+    self.error().check(res)
+  }
+  /// Откатывает текущую транзакцию соединения, отменяя все изменения, сделанные после последней фиксации.
+  ///
+  /// Пока для соединения существует объект [транзакции][2], возвращает ошибку [`TransactionInProgress`][3] --
+  /// транзакцию следует откатить через него.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCITransRollback()`][1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу при каждом вызове.
+  ///
+  /// [1]: http://docs.oracle.com/database/122/LNOCI/transaction-functions.htm#LNOCI17204
+  /// [2]: ./transaction/struct.Transaction.html
+  /// [3]: ./error/enum.Error.html#variant.TransactionInProgress
+  pub fn rollback(&self) -> Result<()> {
+    try!(self.check_no_transaction());
+    self.rollback_impl().map_err(Into::into)
+  }
+  /// Откатывает текущую транзакцию соединения, не проверяя наличие объекта транзакции.
+  fn rollback_impl(&self) -> DbResult<()> {
+    let res = unsafe {
+      OCITransRollback(
+        self.context.native_mut(),
+        self.error().native_mut(),
+        0// Единственный допустимый режим -- OCI_DEFAULT
+      )
+    };
+    self.error().check(res)
+  }
+  /// Проверяет, что для соединения не существует объекта транзакции.
+  #[inline]
+  fn check_no_transaction(&self) -> Result<()> {
+    if self.transaction.get() { Err(Error::TransactionInProgress) } else { Ok(()) }
+  }
+  /// Устанавливает количество строк, получаемых заранее, для всех выражений, которые будут подготовлены данным
+  /// соединением после вызова. Каждое выражение может переопределить данное значение методом
+  /// [`Statement::set_prefetch_rows()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ./stmt/struct.Statement.html#method.set_prefetch_rows
+  #[inline]
+  pub fn set_prefetch_rows(&self, rows: u32) {
+    self.prefetch_rows.set(Some(rows));
+  }
+  /// Устанавливает объем памяти под строки, получаемые заранее, для всех выражений, которые будут подготовлены
+  /// данным соединением после вызова. Каждое выражение может переопределить данное значение методом
+  /// [`Statement::set_prefetch_memory()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ./stmt/struct.Statement.html#method.set_prefetch_memory
+  #[inline]
+  pub fn set_prefetch_memory(&self, bytes: u32) {
+    self.prefetch_memory.set(Some(bytes));
+  }
+  /// Создает объект транзакции, который при выходе из области видимости откатит всю незафиксированную транзакцию
+  /// соединения, если она не будет явно зафиксирована вызовом [`Transaction::commit()`][1]. Откатываются в том числе
+  /// изменения, сделанные до вызова данного метода, поэтому незафиксированные изменения, которые нужно сохранить,
+  /// следует зафиксировать заранее.
+  ///
+  /// У соединения может быть только один объект транзакции: пока он существует, повторный вызов данного метода, а
+  /// также методов [`commit()`][2] и [`rollback()`][3] соединения, возвращает ошибку [`TransactionInProgress`][4].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу, транзакция начинается неявно первым изменяющим данные выражением.
+  ///
+  /// [1]: ./transaction/struct.Transaction.html#method.commit
+  /// [2]: #method.commit
+  /// [3]: #method.rollback
+  /// [4]: ./error/enum.Error.html#variant.TransactionInProgress
+  pub fn transaction(&'e self) -> Result<Transaction<'e>> {
+    try!(self.check_no_transaction());
+    self.transaction.set(true);
+    Ok(Transaction::new(self))
+  }
+  /// Получает сообщения компилятора (ошибки и предупреждения) для хранимого объекта из представления `ALL_ERRORS`.
+  /// Позволяет проверить объекты, скомпилированные ранее, например, ставшие некорректными после изменения объектов,
+  /// от которых они зависят. Сообщения для объектов, скомпилированных с ошибками методом [`Statement::execute()`][1],
+  /// возвращаются в ошибке [`Error::Compile`][2] автоматически.
+  ///
+  /// # Параметры
+  /// - `owner`:
+  ///   Владелец объекта. Если `None`, используется текущий пользователь.
+  /// - `kind`:
+  ///   Тип объекта в том виде, в котором он хранится в словаре данных, например, `PACKAGE BODY`.
+  /// - `name`:
+  ///   Имя объекта в том виде, в котором оно хранится в словаре данных (обычно в верхнем регистре).
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: ./stmt/struct.Statement.html#method.execute
+  /// [2]: ./error/enum.Error.html#variant.Compile
+  pub fn compile_diagnostics(&'e self, owner: Option<&str>, kind: &str, name: &str) -> Result<Vec<Diagnostic>> {
+    let mut stmt = try!(self.prepare("
+      select line, position, nvl(message_number, 0), text, attribute
+        from all_errors
+       where owner = nvl(:owner, user) and type = :kind and name = :name
+       order by sequence"));
+    let rs = try!(stmt.query_with(&(&owner, &kind, &name)));
+
+    let mut result = Vec::new();
+    while let Some(row) = try!(rs.next()) {
+      let attribute: Option<String> = try!(row.get(4));
+      result.push(Diagnostic {
+        line: try!(row.get(0)).unwrap_or(0),
+        column: try!(row.get(1)).unwrap_or(0),
+        code: try!(row.get(2)).unwrap_or(0),
+        text: try!(row.get(3)).unwrap_or_default(),
+        warning: attribute.map_or(false, |a| a == "WARNING"),
+      });
+    }
+    Ok(result)
+  }
+  /// Включает буферизацию вывода `DBMS_OUTPUT` в текущей сессии. Строки, выведенные PL/SQL кодом, выполненным после
+  /// этого вызова, можно получить методом [`take_output()`][1].
+  ///
+  /// # Параметры
+  /// - `buffer_size`:
+  ///   Размер буфера в байтах. Если `None`, размер буфера не ограничен.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу -- вызов процедуры `DBMS_OUTPUT.ENABLE`.
+  ///
+  /// [1]: #method.take_output
+  pub fn enable_output(&'e self, buffer_size: Option<u32>) -> Result<()> {
+    let mut stmt = try!(self.prepare("begin dbms_output.enable(:size); end;"));
+    try!(stmt.execute_with(&[&buffer_size]));
+    Ok(())
+  }
+  /// Выключает буферизацию вывода `DBMS_OUTPUT` в текущей сессии. Строки, еще не полученные из буфера, теряются.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу -- вызов процедуры `DBMS_OUTPUT.DISABLE`.
+  pub fn disable_output(&'e self) -> Result<()> {
+    let stmt = try!(self.prepare("begin dbms_output.disable; end;"));
+    try!(stmt.execute());
+    Ok(())
+  }
+  /// Извлекает все строки, накопленные в буфере `DBMS_OUTPUT` текущей сессии, и очищает его. Пустые строки, которые
+  /// `DBMS_OUTPUT` хранит как `NULL`, возвращаются как пустые строки. Если буферизация вывода не была включена
+  /// методом [`enable_output()`][1], возвращает пустой список.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// conn.enable_output(None).unwrap();
+  /// conn.prepare("begin dbms_output.put_line('hello'); end;").unwrap().execute().unwrap();
+  ///
+  /// assert_eq!(vec!["hello".to_owned()], conn.take_output().unwrap());
+  /// ```
+  ///
+  /// # Запросы к серверу (1..)
+  /// Строки извлекаются процедурой `DBMS_OUTPUT.GET_LINES` порциями по 32 строки, по одному запросу к серверу на порцию.
+  pub fn take_output(&'e self) -> Result<Vec<String>> {
+    let mut stmt = try!(self.prepare("begin dbms_output.get_lines(:lines, :count); end;"));
+    let lines = try!(stmt.bind_table_out::<String, _>(":lines", OUTPUT_LINES));
+    let count = try!(stmt.bind_inout(":count", &(OUTPUT_LINES as i32)));
+
+    let mut result = Vec::new();
+    loop {
+      try!(stmt.execute());
+      let chunk = try!(stmt.get_table(&lines));
+      result.extend(chunk.into_iter().map(Option::unwrap_or_default));
+      // Сервер записывает в `:count` количество реально извлеченных строк, неполная порция означает, что буфер пуст
+      match try!(stmt.get_out(&count)) {
+        Some(n) if n as usize == OUTPUT_LINES => continue,
+        _ => break,
+      }
+    }
+    Ok(result)
+  }
+  /// Закрывает соединение: завершает сессию и отключается от сервера, возвращая первую из возникших при этом ошибок.
+  /// Если соединение было получено из пула, сессия вместо завершения возвращается в пул, а сетевым соединением
+  /// продолжает управлять пул.
+  ///
+  /// В отличие от разрушения объекта соединения, которое игнорирует любые ошибки закрытия, позволяет узнать о них.
+  /// Независимо от результата соединение после вызова считается закрытым, и повторных попыток закрыть его при
+  /// разрушении не делается.
+  ///
+  /// # OCI вызовы
+  /// Осуществляет OCI вызов [`OCISessionEnd()`][end] (или [`OCISessionRelease()`][release] для соединений из пула),
+  /// а затем [`OCIServerDetach()`][detach].
+  ///
+  /// # Запросы к серверу (1-2)
+  /// Функция выполняет один запрос к серверу для завершения сессии и еще один для отключения от сервера, если
+  /// соединение не было получено из пула.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// conn.close().expect("Can't close connection");
+  /// ```
+  ///
+  /// [end]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#LNOCI17123
+  /// [release]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+  /// [detach]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+  pub fn close(mut self) -> Result<()> {
+    self.closed = true;
+    self.detach_cancel();
+    let session = self.end_session();
+    let server = match self.server.take() {
+      Some(server) => server.close(),
+      None => Ok(()),
+    };
+    session.and(server).map_err(Into::into)
+  }
+  /// Отсоединяет от соединения объект отмены запросов, если он был создан.
+  fn detach_cancel(&self) {
+    if let Some(ref handle) = *self.cancel.borrow() {
+      handle.detach();
+    }
+  }
+  /// Завершает сессию пользователя или, если соединение получено из пула, возвращает ее в пул.
+  fn end_session(&self) -> DbResult<()> {
+    let res = if self.pooled {
+      unsafe {
+        OCISessionRelease(
+          self.context.native_mut(),
+          self.error().native_mut(),
+          ptr::null(), 0,// Тег сессии не используется
+          0// Режим OCI_DEFAULT, сессия возвращается в пул
+        )
+      }
+    } else {
+      unsafe {
+        OCISessionEnd(
+          self.context.native_mut(),
+          self.error().native_mut(),
+          self.session.native_mut(),
+          self.auth_mode as u32
+        )
+      }
+    };
+    self.error().check(res)
+  }
+}
+impl<'e> Drop for Connection<'e> {
+  fn drop(&mut self) {
+    if self.closed {
+      return;
+    }
+    self.detach_cancel();
+    // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+    let _ = self.end_session();
   }
 }
 
@@ -369,7 +980,53 @@ trait StatementPrivate {
   ///   синтаксического анализа производится не будет. В этом случае параметр `syntax` не учитывается.
   /// - `syntax`:
   ///   Правила разбора, которые будет использоваться при анализе SQL-выражения.
-  fn new<'c, 'k>(conn: &'c Connection<'c>, sql: &str, key: Option<&'k str>, syntax: Syntax) -> DbResult<Statement<'c, 'k>>;
+  /// - `mode`:
+  ///   Режим поиска выражения в кеше выражений.
+  fn new<'c, 'k>(conn: &'c Connection<'c>, sql: &str, key: Option<&'k str>, syntax: Syntax, mode: CachingMode) -> DbResult<Statement<'c, 'k>>;
+}
+
+/// Типаж, предоставляющий классу соединения возможность создавать транзакции, при этом не выставляя конструктор
+/// транзакции в виде публичного API.
+trait TransactionPrivate<'conn> {
+  /// Создает объект транзакции для указанного соединения.
+  fn new(conn: &'conn Connection<'conn>) -> Self;
+}
+
+/// Типаж, предоставляющий классу соединения возможность создавать объекты отмены запросов и отсоединять их при
+/// закрытии, при этом не выставляя данные возможности в виде публичного API.
+trait CancelHandlePrivate {
+  /// Создает объект отмены запросов для соединения с указанным сервисным контекстом. Для приема ошибок отмены
+  /// используется отдельный хендл, которым объект владеет.
+  fn new(context: *mut OCISvcCtx, error: Handle<OCIError>) -> Self;
+  /// Отсоединяет все копии объекта от соединения, после чего отмена запросов через них ничего не делает.
+  fn detach(&self);
+  /// Проверяет, что объект еще не отсоединен от соединения.
+  fn is_attached(&self) -> bool;
+}
+
+/// Список объектов отмены запросов, созданных для соединений окружения. При разрушении отсоединяет их все, чтобы
+/// объекты, пережившие окружение (например, если соединение было забыто вызовом `mem::forget()` и не отсоединило
+/// их само), не обращались к освобожденным хендлам.
+///
+/// Защищен мьютексом, т.к. соединения, полученные из [пула сессий][1], регистрируют свои объекты из разных потоков.
+///
+/// [1]: ./pool/struct.SessionPool.html
+#[derive(Debug, Default)]
+struct CancelHandles(Mutex<Vec<CancelHandle>>);
+impl CancelHandles {
+  /// Запоминает копию объекта отмены запросов, попутно забывая объекты уже закрытых соединений.
+  fn register(&self, handle: &CancelHandle) {
+    let mut handles = self.0.lock().unwrap_or_else(|e| e.into_inner());
+    handles.retain(CancelHandlePrivate::is_attached);
+    handles.push(handle.clone());
+  }
+}
+impl Drop for CancelHandles {
+  fn drop(&mut self) {
+    for handle in self.0.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+      handle.detach();
+    }
+  }
 }
 
 #[cfg(test)]
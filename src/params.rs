@@ -1,4 +1,10 @@
 //! Содержит структуры, описывающие параметры подключения к базе данных
+use std::error;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+use std::time::Duration;
+
 use types::{AttachMode, AuthMode, Charset, CreateMode};
 
 /// Параметры инициализации менеджера подключений к базе данных.
@@ -26,7 +32,7 @@ impl Into<InitParams> for CreateMode {
   }
 }
 /// Содержит учетные данные пользователя, которые должны использоваться для аутентификации в базе.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Credentials {
   /// База будет проводить аутентификацию по паре пользователь/пароль.
   Rdbms {
@@ -40,8 +46,40 @@ pub enum Credentials {
   Ext,
   //Proxy,
 }
-/// Параметры подключения к базе данных
-#[derive(Clone, Debug)]
+/// Параметры подключения к базе данных.
+///
+/// Параметры могут быть получены разбором строки подключения (через типаж `FromStr`, т.е. вызовом метода `parse()`
+/// у строки). Поддерживаются следующие форматы:
+///
+/// - `user/pass@host:port/service` и `user/pass@//host/service:server` -- строки в формате [EZConnect][ez];
+/// - `user/pass@(DESCRIPTION=...)` -- полный TNS-дескриптор;
+/// - `user/pass@alias` -- псевдоним из `tnsnames.ora`;
+/// - `jdbc:oracle:thin:[user/pass]@host:port:sid` -- JDBC URL, SID при этом преобразуется в TNS-дескриптор.
+///   Также поддерживаются JDBC URL, в которых после `@` указана EZConnect строка или TNS-дескриптор;
+/// - `/` или `/@dblink` -- подключение с внешней аутентификацией.
+///
+/// Имя пользователя и пароль, содержащие символы `/`, `@` или пробелы, должны заключаться в двойные кавычки, как это
+/// делается в sql*plus. Двойная кавычка внутри кавычек записывается удвоенной (`""`). В конце строки может быть указан режим аутентификации `as sysdba` или `as sysoper`.
+///
+/// Обратное преобразование в строку выполняется реализацией типажа `Display` и дает строку в формате sql*plus, из
+/// которой можно снова получить те же самые параметры. Следует учитывать, что в этом случае в строку попадает пароль,
+/// а режимы подключения и аутентификации, не имеющие строкового представления, теряются.
+///
+/// # Пример
+/// ```
+/// # use oci::params::{ConnectParams, Credentials};
+/// # use oci::types::AuthMode;
+/// let params: ConnectParams = "/ as sysdba".parse().unwrap();
+/// assert_eq!(Credentials::Ext, params.credentials);
+/// assert_eq!(AuthMode::SysDba, params.auth_mode);
+///
+/// let params: ConnectParams = "scott/tiger@localhost:1521/orcl".parse().unwrap();
+/// assert_eq!("localhost:1521/orcl", params.dblink);
+/// assert_eq!("scott/tiger@localhost:1521/orcl", params.to_string());
+/// ```
+///
+/// [ez]: http://docs.oracle.com/database/122/NETAG/configuring-naming-methods.htm#NETAG255
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConnectParams {
   /// Адрес базы и указатель сервиса, к которому следует подключиться.
   /// В случае внешней аутентификации не требуется, т.к. база всегда запущена на той же машине
@@ -52,4 +90,282 @@ pub struct ConnectParams {
   pub credentials: Credentials,
   /// Режим аутентификации, позволяющий задать дополнительные привелегии при подключении к базе данных.
   pub auth_mode: AuthMode,
-}
\ No newline at end of file
+}
+impl FromStr for ConnectParams {
+  type Err = ParseConnectParamsError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    if s.is_empty() {
+      return Err(ParseConnectParamsError::Empty);
+    }
+    let (s, auth_mode) = try!(split_auth_mode(s));
+    let (s, jdbc) = strip_jdbc_prefix(s);
+
+    let (cred, link) = try!(split_unquoted(s, '@'));
+    let credentials = try!(parse_credentials(cred.trim()));
+    let dblink = match link.map(str::trim) {
+      Some(link) if jdbc => try!(jdbc_dblink(link)),
+      Some(link) => link.to_owned(),
+      None => String::new(),
+    };
+    if dblink.starts_with('(') {
+      try!(check_parens(&dblink));
+    }
+
+    Ok(ConnectParams {
+      dblink: dblink,
+      attach_mode: AttachMode::default(),
+      credentials: credentials,
+      auth_mode: auth_mode,
+    })
+  }
+}
+impl fmt::Display for ConnectParams {
+  /// Распечатывает параметры в виде строки подключения в формате sql*plus, из которой они могут быть
+  /// распарсены обратно при помощи типажа `FromStr`.
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.credentials {
+      Credentials::Rdbms { ref username, ref password } => {
+        try!(write_quoted(f, username));
+        try!(f.write_str("/"));
+        try!(write_quoted(f, password));
+      },
+      Credentials::Ext => try!(f.write_str("/")),
+    }
+    if !self.dblink.is_empty() {
+      try!(write!(f, "@{}", self.dblink));
+    }
+    match self.auth_mode {
+      AuthMode::SysDba  => f.write_str(" as sysdba"),
+      AuthMode::SysOper => f.write_str(" as sysoper"),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// Параметры создания пула соединений.
+///
+/// Пул соединений держит несколько физических соединений к серверу, поверх которых мультиплексируются сессии,
+/// подключенные к пулу в режиме [`AttachMode::CPool`][1]. Указанные в параметрах учетные данные используются
+/// только для служебных сессий самого пула, сессии пользователей аутентифицируются независимо.
+///
+/// [1]: ../types/enum.AttachMode.html#variant.CPool
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionPoolParams {
+  /// Адрес базы и указатель сервиса, к которому следует подключиться.
+  pub dblink: String,
+  /// Имя пользователя, под которым пул устанавливает свои соединения.
+  pub username: String,
+  /// Пароль пользователя, под которым пул устанавливает свои соединения.
+  pub password: String,
+  /// Минимальное количество физических соединений в пуле.
+  pub min: u32,
+  /// Максимальное количество физических соединений в пуле.
+  pub max: u32,
+  /// Количество соединений, которое будет открыто за раз, если свободных соединений в пуле не осталось.
+  pub increment: u32,
+}
+impl Default for ConnectionPoolParams {
+  fn default() -> Self {
+    ConnectionPoolParams {
+      dblink: String::new(),
+      username: String::new(),
+      password: String::new(),
+      min: 1,
+      max: 10,
+      increment: 1,
+    }
+  }
+}
+/// Параметры создания пула сессий.
+///
+/// Все сессии пула создаются с одними и теми же учетными данными (т.н. гомогенный пул), поэтому аутентификация
+/// возможна только по паре пользователь/пароль.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionPoolParams {
+  /// Адрес базы и указатель сервиса, к которому следует подключиться.
+  pub dblink: String,
+  /// Имя пользователя, под которым будут создаваться сессии пула.
+  pub username: String,
+  /// Пароль пользователя, под которым будут создаваться сессии пула.
+  pub password: String,
+  /// Минимальное количество сессий в пуле. Данное количество сессий открывается сразу при создании пула.
+  pub min: u32,
+  /// Максимальное количество сессий в пуле.
+  pub max: u32,
+  /// Количество сессий, которое будет открыто за раз, если свободных сессий в пуле не осталось.
+  pub increment: u32,
+  /// Время ожидания освобождения сессии в случае, если все сессии пула заняты и пул достиг максимального размера.
+  /// `None` означает ожидание без ограничения по времени, нулевое значение -- немедленное получение ошибки.
+  ///
+  /// Oracle принимает время ожидания в миллисекундах, поэтому дробная часть миллисекунды округляется вверх, а значения,
+  /// превышающие `u32::MAX` миллисекунд (около 49 дней), ограничиваются этим максимумом.
+  ///
+  /// Ненулевое время ожидания поддерживается только клиентской библиотекой версии 12.2 и выше. При использовании более
+  /// старого клиента создание пула вернет ошибку [`Error::UnsupportedClient`][err].
+  ///
+  /// [err]: ../error/enum.Error.html#variant.UnsupportedClient
+  pub wait_timeout: Option<Duration>,
+}
+impl Default for SessionPoolParams {
+  fn default() -> Self {
+    SessionPoolParams {
+      dblink: String::new(),
+      username: String::new(),
+      password: String::new(),
+      min: 1,
+      max: 10,
+      increment: 1,
+      wait_timeout: None,
+    }
+  }
+}
+/// Возможные ошибки разбора строки подключения к базе данных.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseConnectParamsError {
+  /// Строка подключения пуста или состоит только из пробелов.
+  Empty,
+  /// Открывающая двойная кавычка в имени пользователя или пароле не имеет парной закрывающей.
+  UnclosedQuote,
+  /// Указан пароль, но не указано имя пользователя (строка вида `/password@dblink`).
+  NoUsername,
+  /// Указано имя пользователя, но не указан пароль (строка вида `user@dblink`).
+  NoPassword,
+  /// После ключевого слова `as` указан неизвестный режим аутентификации. Поддерживаются только `sysdba` и `sysoper`.
+  UnknownAuthMode(String),
+  /// Порт в JDBC URL не является числом в диапазоне `[0; 65535]`.
+  InvalidPort(ParseIntError),
+  /// Количество открывающих и закрывающих скобок в TNS-дескрипторе не совпадает.
+  UnbalancedParens,
+}
+impl fmt::Display for ParseConnectParamsError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+impl error::Error for ParseConnectParamsError {
+  fn description(&self) -> &str {
+    match *self {
+      ParseConnectParamsError::Empty => "Empty connect string",
+      ParseConnectParamsError::UnclosedQuote => "Unclosed quote in username or password",
+      ParseConnectParamsError::NoUsername => "Password specified without username",
+      ParseConnectParamsError::NoPassword => "Username specified without password",
+      ParseConnectParamsError::UnknownAuthMode(_) => "Unknown authentication mode, expected `sysdba` or `sysoper`",
+      ParseConnectParamsError::InvalidPort(_) => "Invalid port in JDBC URL",
+      ParseConnectParamsError::UnbalancedParens => "Unbalanced parentheses in TNS descriptor",
+    }
+  }
+}
+
+/// Отделяет от строки подключения суффикс ` as sysdba`/` as sysoper` и возвращает оставшуюся часть строки
+/// и режим аутентификации, заданный суффиксом.
+fn split_auth_mode(s: &str) -> Result<(&str, AuthMode), ParseConnectParamsError> {
+  let lower = s.to_ascii_lowercase();
+  if let Some(pos) = lower.rfind(" as ") {
+    let mode = lower[pos + 4..].trim();
+    // Если после `as` идет не одно слово, то это часть дескриптора или пароля в кавычках, а не режим
+    if mode.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+      let mode = match mode {
+        "sysdba"  => AuthMode::SysDba,
+        "sysoper" => AuthMode::SysOper,
+        m => return Err(ParseConnectParamsError::UnknownAuthMode(m.to_owned())),
+      };
+      return Ok((s[..pos].trim_end(), mode));
+    }
+  }
+  Ok((s, AuthMode::default()))
+}
+/// Отделяет от строки префикс JDBC URL. Возвращает оставшуюся часть строки и признак того, был ли префикс.
+fn strip_jdbc_prefix(s: &str) -> (&str, bool) {
+  for prefix in &["jdbc:oracle:thin:", "jdbc:oracle:oci:"] {
+    if s.len() >= prefix.len() && s[..prefix.len()].eq_ignore_ascii_case(prefix) {
+      return (&s[prefix.len()..], true);
+    }
+  }
+  (s, false)
+}
+/// Разделяет строку по первому вхождению указанного символа, находящемуся вне двойных кавычек.
+fn split_unquoted(s: &str, sep: char) -> Result<(&str, Option<&str>), ParseConnectParamsError> {
+  let mut quoted = false;
+  for (i, c) in s.char_indices() {
+    match c {
+      '"' => quoted = !quoted,
+      c if c == sep && !quoted => return Ok((&s[..i], Some(&s[i + c.len_utf8()..]))),
+      _ => {},
+    }
+  }
+  if quoted {
+    return Err(ParseConnectParamsError::UnclosedQuote);
+  }
+  Ok((s, None))
+}
+/// Снимает с имени пользователя или пароля двойные кавычки, если они есть. Удвоенные кавычки внутри кавычек
+/// заменяются одинарными.
+fn unquote(s: &str) -> Result<String, ParseConnectParamsError> {
+  if !s.starts_with('"') {
+    return Ok(s.to_owned());
+  }
+  if s.len() < 2 || !s.ends_with('"') {
+    return Err(ParseConnectParamsError::UnclosedQuote);
+  }
+  Ok(s[1..s.len() - 1].replace("\"\"", "\""))
+}
+/// Разбирает часть строки подключения, содержащую учетные данные (все, что до символа `@`).
+fn parse_credentials(s: &str) -> Result<Credentials, ParseConnectParamsError> {
+  let (user, pass) = try!(split_unquoted(s, '/'));
+  let user = try!(unquote(user.trim()));
+  let pass = match pass {
+    Some(p) => Some(try!(unquote(p.trim()))),
+    None => None,
+  };
+  match (user.is_empty(), pass) {
+    // Пустая строка (в JDBC URL учетные данные могут отсутствовать) или `/`
+    (true, None) => Ok(Credentials::Ext),
+    (true, Some(ref pass)) if pass.is_empty() => Ok(Credentials::Ext),
+    (true, Some(_)) => Err(ParseConnectParamsError::NoUsername),
+    (false, None) => Err(ParseConnectParamsError::NoPassword),
+    (false, Some(pass)) => Ok(Credentials::Rdbms { username: user, password: pass }),
+  }
+}
+/// Преобразует адрес базы из JDBC URL в строку, понятную Oracle. Формат `host:port:sid` не поддерживается
+/// EZConnect-ом, поэтому он преобразуется в TNS-дескриптор, остальные форматы используются как есть.
+fn jdbc_dblink(link: &str) -> Result<String, ParseConnectParamsError> {
+  if link.starts_with('(') || link.contains('/') {
+    return Ok(link.to_owned());
+  }
+  let parts: Vec<&str> = link.split(':').collect();
+  if parts.len() != 3 {
+    return Ok(link.to_owned());
+  }
+  let port = try!(u16::from_str(parts[1]).map_err(ParseConnectParamsError::InvalidPort));
+  Ok(format!(
+    "(DESCRIPTION=(ADDRESS=(PROTOCOL=TCP)(HOST={})(PORT={}))(CONNECT_DATA=(SID={})))",
+    parts[0], port, parts[2]
+  ))
+}
+/// Проверяет, что все открытые скобки в TNS-дескрипторе закрыты.
+fn check_parens(s: &str) -> Result<(), ParseConnectParamsError> {
+  let mut depth = 0usize;
+  for c in s.chars() {
+    match c {
+      '(' => depth += 1,
+      ')' if depth == 0 => return Err(ParseConnectParamsError::UnbalancedParens),
+      ')' => depth -= 1,
+      _ => {},
+    }
+  }
+  match depth {
+    0 => Ok(()),
+    _ => Err(ParseConnectParamsError::UnbalancedParens),
+  }
+}
+/// Записывает имя пользователя или пароль, при необходимости заключая их в кавычки.
+fn write_quoted(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+  if s.is_empty() || s.contains(|c: char| c == '/' || c == '@' || c == '"' || c.is_whitespace()) {
+    // Кавычки внутри кавычек удваиваются, как в идентификаторах SQL
+    write!(f, "\"{}\"", s.replace('"', "\"\""))
+  } else {
+    f.write_str(s)
+  }
+}
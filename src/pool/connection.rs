@@ -0,0 +1,173 @@
+use std::ptr;
+use std::slice;
+
+use {Connection, Environment, DbResult, Result};
+use params::{ConnectionPoolParams, ConnectParams, Credentials};
+use types::{AttachMode, AuthMode};
+
+use ffi::Handle;// Основные типобезопасные примитивы
+
+use ffi::attr::AttrHolder;
+use ffi::native::{OCIEnv, OCICPool};// FFI типы
+use ffi::native::{OCIConnectionPoolCreate, OCIConnectionPoolDestroy};// FFI функции
+use ffi::types::Attr;
+
+//-------------------------------------------------------------------------------------------------
+/// Пул соединений, позволяющий множеству сессий работать через небольшое количество физических соединений к серверу.
+/// В отличие от [пула сессий][spool], сессии создаются и аутентифицируются каждый раз заново, но не требуют установки
+/// сетевого соединения, и могут принадлежать разным пользователям.
+///
+/// Для подключения через пул необходимо либо воспользоваться методом [`connect()`][connect], либо вызвать
+/// [`Environment::connect()`][env], указав в параметрах режим [`AttachMode::CPool`][cpool] и [имя пула][name] в
+/// качестве адреса базы. В последнем случае следует самостоятельно следить за тем, чтобы пул жил дольше соединения.
+///
+/// # OCI вызовы
+/// Пул создается OCI вызовом [`OCIConnectionPoolCreate()`][new]. При разрушении объекта будет осуществлен OCI вызов
+/// [`OCIConnectionPoolDestroy()`][end].
+///
+/// # Пример
+/// ```no_run
+/// # use oci::Environment;
+/// # use oci::params::{ConnectionPoolParams, Credentials};
+/// # use oci::pool::ConnectionPool;
+/// # use oci::types::AuthMode;
+/// let env = Environment::default();
+/// let pool = ConnectionPool::new(&env, ConnectionPoolParams {
+///   dblink: "localhost:1521/XE".into(),
+///   username: "pool_user".into(),
+///   password: "pool_password".into(),
+///   ..Default::default()
+/// }).unwrap();
+/// let credentials = Credentials::Rdbms { username: "username".into(), password: "password".into() };
+/// let conn = pool.connect(credentials, AuthMode::Default).unwrap();
+/// conn.prepare("select * from dual").unwrap().query().unwrap();
+/// ```
+///
+/// [spool]: ./struct.SessionPool.html
+/// [connect]: #method.connect
+/// [env]: ../struct.Environment.html#method.connect
+/// [cpool]: ../types/enum.AttachMode.html#variant.CPool
+/// [name]: #method.name
+/// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+/// [end]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+#[derive(Debug)]
+pub struct ConnectionPool<'e> {
+  /// Окружение, в рамках которого создан пул.
+  env: &'e Environment<'e>,
+  /// Хендл пула соединений.
+  handle: Handle<OCICPool>,
+  /// Имя пула, назначенное ему Oracle при создании. Используется в качестве адреса базы при подключении через пул.
+  name: String,
+}
+impl<'e> ConnectionPool<'e> {
+  /// Создает пул соединений с указанными параметрами. Сразу после создания пул открывает минимальное количество
+  /// соединений, указанное в параметрах.
+  ///
+  /// # OCI вызовы
+  /// Осуществляет OCI вызов [`OCIConnectionPoolCreate()`][new].
+  ///
+  /// # Запросы к серверу (1..)
+  /// Функция выполняет запросы к серверу для открытия минимального количества соединений пула.
+  ///
+  /// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+  pub fn new(env: &'e Environment<'e>, params: ConnectionPoolParams) -> Result<Self> {
+    let handle: Handle<OCICPool> = try!(env.new_handle());
+    let mut name: *const u8 = ptr::null();
+    let mut len = 0;
+    let res = unsafe {
+      OCIConnectionPoolCreate(
+        env.native() as *mut OCIEnv,
+        env.error.native_mut(),
+        handle.native_mut(),
+        &mut name, &mut len,
+        params.dblink.as_ptr(), params.dblink.len() as i32,
+        params.min, params.max, params.increment,
+        params.username.as_ptr(), params.username.len() as i32,
+        params.password.as_ptr(), params.password.len() as i32,
+        0// Режим OCI_DEFAULT, создается новый пул
+      )
+    };
+    try!(env.error.check(res));
+    // Имя пула принадлежит хендлу пула, поэтому его нужно скопировать
+    let name = unsafe { slice::from_raw_parts(name, len as usize) };
+    let name = String::from_utf8_lossy(name).into_owned();
+
+    Ok(ConnectionPool { env: env, handle: handle, name: name })
+  }
+  /// Осуществляет подключение к базе данных через пул с указанными учетными данными. Сессия мультиплексируется через
+  /// одно из физических соединений пула и закрывается при разрушении соединения.
+  ///
+  /// # OCI вызовы
+  /// Осуществляет OCI вызовы [`OCIServerAttach()`][new1] в режиме `OCI_CPOOL` и [`OCISessionBegin()`][new2]. При
+  /// разрушении объекта соединения будет осуществлен сначала OCI вызов [`OCISessionEnd()`][end2], а затем
+  /// [`OCIServerDetach()`][end1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу для аутентификации сессии. Также будет совершен один запрос к серверу
+  /// при уничтожении соединения.
+  ///
+  /// [new1]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#GUID-B6291228-DA2F-4CE9-870A-F94243141757
+  /// [end1]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#LNOCI17121
+  /// [new2]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#GUID-31B1FDB3-056E-4AF9-9B89-8DA6AA156947
+  /// [end2]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm#LNOCI17123
+  pub fn connect<'p>(&'p self, credentials: Credentials, auth_mode: AuthMode) -> Result<Connection<'p>> {
+    let params = ConnectParams {
+      dblink: self.name.clone(),
+      attach_mode: AttachMode::CPool,
+      credentials: credentials,
+      auth_mode: auth_mode,
+    };
+    Connection::new(self.env, &params).map_err(Into::into)
+  }
+  /// Получает имя пула, назначенное ему Oracle при создании. Данное имя необходимо указывать в качестве адреса
+  /// базы при подключении через пул в режиме [`AttachMode::CPool`][1].
+  ///
+  /// [1]: ../types/enum.AttachMode.html#variant.CPool
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+  /// Получает количество физических соединений, открытых в пуле в данный момент.
+  #[inline]
+  pub fn open_count(&self) -> Result<u32> {
+    self.get_attr(Attr::ConnOpenCount)
+  }
+  /// Получает количество физических соединений пула, используемых в данный момент.
+  #[inline]
+  pub fn busy_count(&self) -> Result<u32> {
+    self.get_attr(Attr::ConnBusyCount)
+  }
+  /// Получает минимальное количество соединений в пуле.
+  #[inline]
+  pub fn min(&self) -> Result<u32> {
+    self.get_attr(Attr::ConnMin)
+  }
+  /// Получает максимальное количество соединений в пуле.
+  #[inline]
+  pub fn max(&self) -> Result<u32> {
+    self.get_attr(Attr::ConnMax)
+  }
+  /// Получает количество соединений, на которое увеличивается пул, когда свободных соединений не осталось.
+  #[inline]
+  pub fn increment(&self) -> Result<u32> {
+    self.get_attr(Attr::ConnIncr)
+  }
+
+  fn get_attr(&self, attr: Attr) -> Result<u32> {
+    let res: DbResult<u32> = self.handle.get_(attr, &self.env.error);
+    res.map_err(Into::into)
+  }
+}
+impl<'e> Drop for ConnectionPool<'e> {
+  fn drop(&mut self) {
+    let res = unsafe {
+      OCIConnectionPoolDestroy(
+        self.handle.native_mut(),
+        self.env.error.native_mut(),
+        0// Единственный допустимый режим -- OCI_DEFAULT
+      )
+    };
+    // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+    let _ = self.env.error.check(res);
+  }
+}
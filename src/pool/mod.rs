@@ -0,0 +1,7 @@
+//! Содержит пулы, позволяющие повторно использовать однажды созданные сессии и сетевые соединения к серверу базы
+//! данных вместо того, чтобы устанавливать их заново для каждого подключения.
+mod connection;
+mod session;
+
+pub use self::connection::ConnectionPool;
+pub use self::session::SessionPool;
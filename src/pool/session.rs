@@ -0,0 +1,229 @@
+use std::cmp;
+use std::ptr;
+use std::slice;
+use std::time::Duration;
+
+use {Connection, Environment, DbResult, Result};
+use error::Error;
+use params::SessionPoolParams;
+use types::CreateMode;
+use version::{client_version, Version};
+
+use ffi::Handle;// Основные типобезопасные примитивы
+
+use ffi::attr::AttrHolder;
+use ffi::native::{OCIEnv, OCIError, OCISPool, OCISvcCtx};// FFI типы
+use ffi::native::{OCISessionPoolCreate, OCISessionPoolDestroy, OCISessionGet};// FFI функции
+use ffi::types::Attr;
+use ffi::types::{SessionPoolMode, SessionGetMode, SessionPoolGetMode};
+
+//-------------------------------------------------------------------------------------------------
+/// Пул сессий, позволяющий не устанавливать соединение и не проходить аутентификацию заново при каждом подключении
+/// к базе данных. Все сессии пула принадлежат одному и тому же пользователю.
+///
+/// Соединение, полученное из пула вызовом [`get()`][get], при разрушении не закрывается, а возвращается в пул и
+/// может быть повторно выдано следующему вызову [`get()`][get].
+///
+/// Пул реализует типаж `Sync`, поэтому несколько потоков могут одновременно получать из него сессии. Каждое полученное
+/// соединение пользуется собственным хендлом ошибок, а не общим хендлом [окружения][env], и может работать параллельно
+/// с соединениями других потоков. Для этого окружение должно быть создано в режиме [`CreateMode::Threaded`][threaded],
+/// в противном случае создание пула вернет ошибку [`Error::NotThreaded`][err].
+///
+/// # OCI вызовы
+/// Пул создается OCI вызовом [`OCISessionPoolCreate()`][new]. При разрушении объекта будет осуществлен OCI вызов
+/// [`OCISessionPoolDestroy()`][end].
+///
+/// # Пример
+/// ```no_run
+/// # use oci::Environment;
+/// # use oci::params::SessionPoolParams;
+/// # use oci::pool::SessionPool;
+/// # use oci::types::CreateMode;
+/// let env = Environment::new(CreateMode::Threaded).unwrap();
+/// let pool = SessionPool::new(&env, SessionPoolParams {
+///   dblink: "localhost:1521/XE".into(),
+///   username: "username".into(),
+///   password: "password".into(),
+///   ..Default::default()
+/// }).unwrap();
+/// {
+///   let conn = pool.get().unwrap();
+///   conn.prepare("select * from dual").unwrap().query().unwrap();
+///   // При выходе из области видимости сессия вернется в пул
+/// }
+/// println!("busy: {}, open: {}", pool.busy_count().unwrap(), pool.open_count().unwrap());
+/// ```
+///
+/// [get]: #method.get
+/// [env]: ../struct.Environment.html
+/// [threaded]: ../types/enum.CreateMode.html#variant.Threaded
+/// [err]: ../error/enum.Error.html#variant.NotThreaded
+/// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+/// [end]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+#[derive(Debug)]
+pub struct SessionPool<'e> {
+  /// Окружение, в рамках которого создан пул.
+  env: &'e Environment<'e>,
+  /// Хендл пула сессий.
+  handle: Handle<OCISPool>,
+  /// Имя пула, назначенное ему Oracle при создании. Используется для получения сессий из пула.
+  name: String,
+}
+impl<'e> SessionPool<'e> {
+  /// Создает пул сессий с указанными параметрами. Сразу после создания пул открывает минимальное количество сессий,
+  /// указанное в параметрах.
+  ///
+  /// Окружение должно быть создано в режиме [`CreateMode::Threaded`][threaded], в противном случае возвращается ошибка
+  /// [`Error::NotThreaded`][err]. Ненулевое время ожидания сессии `wait_timeout` требует клиента версии 12.2 и выше,
+  /// для более старого клиента пул не создается и возвращается ошибка [`Error::UnsupportedClient`][client].
+  ///
+  /// # OCI вызовы
+  /// Осуществляет OCI вызов [`OCISessionPoolCreate()`][new] и устанавливает атрибуты пула вызовами [`OCIAttrSet()`][set].
+  ///
+  /// # Запросы к серверу (1..)
+  /// Функция выполняет запросы к серверу для открытия минимального количества сессий пула.
+  ///
+  /// [threaded]: ../types/enum.CreateMode.html#variant.Threaded
+  /// [err]: ../error/enum.Error.html#variant.NotThreaded
+  /// [client]: ../error/enum.Error.html#variant.UnsupportedClient
+  /// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+  /// [set]: http://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17131
+  pub fn new(env: &'e Environment<'e>, params: SessionPoolParams) -> Result<Self> {
+    if env.env.params().mode != CreateMode::Threaded {
+      return Err(Error::NotThreaded);
+    }
+    let (mode, timeout) = match params.wait_timeout {
+      None => (SessionPoolGetMode::Wait, None),
+      Some(ref t) if *t == Duration::from_secs(0) => (SessionPoolGetMode::NoWait, None),
+      Some(t) => (SessionPoolGetMode::TimedWait, Some(t)),
+    };
+    // Режим ожидания с таймаутом появился в 12.2, более старый клиент отвергает его невнятной ошибкой ORA-24315
+    if timeout.is_some() {
+      let required = Version::minor(12, 2);
+      let client = client_version();
+      if client < required {
+        return Err(Error::UnsupportedClient { required: required, client: client });
+      }
+    }
+    let mut handle: Handle<OCISPool> = try!(env.new_handle());
+    let mut name: *const u8 = ptr::null();
+    let mut len = 0;
+    let res = unsafe {
+      OCISessionPoolCreate(
+        env.native() as *mut OCIEnv,
+        env.error.native_mut(),
+        handle.native_mut(),
+        &mut name, &mut len,
+        params.dblink.as_ptr(), params.dblink.len() as u32,
+        params.min, params.max, params.increment,
+        params.username.as_ptr(), params.username.len() as u32,
+        params.password.as_ptr(), params.password.len() as u32,
+        SessionPoolMode::Homogeneous as u32
+      )
+    };
+    try!(env.error.check(res));
+    // Имя пула принадлежит хендлу пула, поэтому его нужно скопировать
+    let name = unsafe { slice::from_raw_parts(name, len as usize) };
+    let name = String::from_utf8_lossy(name).into_owned();
+
+    try!(handle.set_(mode as u8, Attr::SPoolGetMode, &env.error));
+    if let Some(t) = timeout {
+      // Округляем вверх, чтобы ненулевое время ожидания не превратилось в нулевое
+      let ms = t.as_secs().saturating_mul(1000).saturating_add(((t.subsec_nanos() + 999_999) / 1_000_000) as u64);
+      try!(handle.set_(cmp::min(ms, u32::MAX as u64) as u32, Attr::SPoolWaitTimeout, &env.error));
+    }
+
+    Ok(SessionPool { env: env, handle: handle, name: name })
+  }
+  /// Получает свободную сессию из пула. Если свободных сессий нет, но пул еще не достиг максимального размера, то
+  /// в нем будут открыты новые сессии, иначе поведение определяется параметром `wait_timeout`, указанным при
+  /// создании пула.
+  ///
+  /// Полученное соединение при разрушении возвращает сессию обратно в пул. Метод можно вызывать одновременно из
+  /// нескольких потоков.
+  ///
+  /// # OCI вызовы
+  /// Осуществляет OCI вызов [`OCISessionGet()`][new]. При разрушении объекта соединения будет осуществлен OCI вызов
+  /// [`OCISessionRelease()`][end].
+  ///
+  /// # Запросы к серверу (0..)
+  /// Если в пуле есть свободная сессия, функция не выполняет запросов к серверу.
+  ///
+  /// [new]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+  /// [end]: http://docs.oracle.com/database/122/LNOCI/connect-authorize-and-initialize-functions.htm
+  pub fn get<'p>(&'p self) -> Result<Connection<'p>> {
+    // Хендлом ошибок окружения в это время может пользоваться другой поток, поэтому для каждой сессии заводится свой.
+    // Ошибка его создания записывается в хендл окружения, а не в общий хендл ошибок
+    let error: Handle<OCIError> = try!(self.env.env.new_error_handle());
+    let mut context: *mut OCISvcCtx = ptr::null_mut();
+    let res = unsafe {
+      OCISessionGet(
+        self.env.native() as *mut OCIEnv,
+        error.native_mut(),
+        &mut context,
+        ptr::null_mut(),// Учетные данные для гомогенного пула не указываются
+        self.name.as_ptr(), self.name.len() as u32,
+        ptr::null(), 0,// Теги сессий не используются
+        ptr::null_mut(), ptr::null_mut(),
+        ptr::null_mut(),
+        SessionGetMode::SPool as u32
+      )
+    };
+    try!(error.check(res));
+    Connection::from_pool(self.env, context, error).map_err(Into::into)
+  }
+  /// Получает имя пула, назначенное ему Oracle при создании.
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+  /// Получает количество сессий, открытых в пуле в данный момент.
+  #[inline]
+  pub fn open_count(&self) -> Result<u32> {
+    self.get_attr(Attr::SPoolOpenCount)
+  }
+  /// Получает количество сессий пула, используемых в данный момент.
+  #[inline]
+  pub fn busy_count(&self) -> Result<u32> {
+    self.get_attr(Attr::SPoolBusyCount)
+  }
+  /// Получает минимальное количество сессий в пуле.
+  #[inline]
+  pub fn min(&self) -> Result<u32> {
+    self.get_attr(Attr::SPoolMin)
+  }
+  /// Получает максимальное количество сессий в пуле.
+  #[inline]
+  pub fn max(&self) -> Result<u32> {
+    self.get_attr(Attr::SPoolMax)
+  }
+  /// Получает количество сессий, на которое увеличивается пул, когда свободных сессий не осталось.
+  #[inline]
+  pub fn increment(&self) -> Result<u32> {
+    self.get_attr(Attr::SPoolIncr)
+  }
+
+  fn get_attr(&self, attr: Attr) -> Result<u32> {
+    // По той же причине, что и в `get()`, общий хендл ошибок окружения не используется
+    let error: Handle<OCIError> = try!(self.env.env.new_error_handle());
+    let res: DbResult<u32> = self.handle.get_(attr, &error);
+    res.map_err(Into::into)
+  }
+}
+// Окружение создано в режиме `OCI_THREADED`, в котором OCI сам защищает хендлы пула и окружения мьютексами. Методы,
+// доступные по разделяемой ссылке, и полученные из пула соединения пользуются собственными хендлами ошибок, ошибки
+// создания которых записываются в хендл окружения, поэтому общий хендл ошибок окружения из разных потоков не трогается.
+unsafe impl<'e> Sync for SessionPool<'e> {}
+impl<'e> Drop for SessionPool<'e> {
+  fn drop(&mut self) {
+    let res = unsafe {
+      OCISessionPoolDestroy(
+        self.handle.native_mut(),
+        self.env.error.native_mut(),
+        0// Режим OCI_DEFAULT, занятые сессии не закрываются принудительно
+      )
+    };
+    // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+    let _ = self.env.error.check(res);
+  }
+}
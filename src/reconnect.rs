@@ -0,0 +1,214 @@
+//! Содержит обертку над соединением, автоматически восстанавливающую его после потери связи с сервером.
+
+use std::mem;
+
+use {Connection, Environment, Result};
+use params::ConnectParams;
+use stmt::Statement;
+
+/// Соединение, которое автоматически переподключается к серверу, если выполняемая через него работа завершилась
+/// ошибкой потери связи (такой, для которой [`Error::is_connection_lost()`][lost] возвращает `true`, например,
+/// `ORA-03113` или `ORA-03135`). Параметры подключения запоминаются при создании обертки и используются повторно.
+///
+/// Так как подготовленные выражения не могут пережить соединение, их породившее, работа с базой выполняется внутри
+/// замыканий, передаваемых в методы [`run()`][run] и [`with_statement()`][with]. При потере связи создается новое
+/// соединение, выражение заново подготавливается по своему тексту и замыкание вызывается еще раз. Повтор выполняется
+/// только один раз: если и он завершился ошибкой, она возвращается вызывающему коду.
+///
+/// Вместе с соединением теряются его незафиксированная транзакция и состояние сессии (переменные пакетов, настройки
+/// `alter session` и т.п.), поэтому повторно выполняемые замыкания должны быть готовы выполняться с самого начала.
+/// Настройки соединения, установленные методами самой обертки ([`set_prefetch_rows()`][rows],
+/// [`set_prefetch_memory()`][memory], [`set_statement_cache_size()`][cache] и [`enable_output()`][output]), запоминаются
+/// и заново применяются к каждому новому соединению. Настройки, установленные напрямую через [`connection()`][conn],
+/// после переподключения теряются.
+///
+/// # Пример
+/// ```no_run
+/// # use oci::Environment;
+/// # use oci::params::{ConnectParams, Credentials};
+/// # use oci::reconnect::ReconnectingConnection;
+/// let env = Environment::default();
+/// let params = ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() };
+/// let mut conn = ReconnectingConnection::new(&env, params).unwrap();
+/// let count: i32 = conn.with_statement("select count(*) from test_bind", |stmt| {
+///   let rs = try!(stmt.query());
+///   let row = try!(rs.next()).expect("Nothing fetch");
+///   row.get(0).map(Option::unwrap)
+/// }).unwrap();
+/// ```
+///
+/// [lost]: ../error/enum.Error.html#method.is_connection_lost
+/// [run]: #method.run
+/// [with]: #method.with_statement
+/// [rows]: #method.set_prefetch_rows
+/// [memory]: #method.set_prefetch_memory
+/// [cache]: #method.set_statement_cache_size
+/// [output]: #method.enable_output
+/// [conn]: #method.connection
+#[derive(Debug)]
+pub struct ReconnectingConnection<'e> {
+  /// Окружение, в рамках которого создаются соединения.
+  env: &'e Environment<'e>,
+  /// Параметры, с которыми было создано первое соединение и с которыми будут создаваться последующие.
+  params: ConnectParams,
+  /// Настройки, применяемые к каждому новому соединению.
+  settings: Settings,
+  /// Текущее соединение.
+  conn: Connection<'e>,
+}
+impl<'e> ReconnectingConnection<'e> {
+  /// Осуществляет подключение к базе данных с указанными параметрами и запоминает их для повторных подключений.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу для создания соединения.
+  pub fn new<P: Into<ConnectParams>>(env: &'e Environment<'e>, params: P) -> Result<Self> {
+    let params = params.into();
+    let conn = try!(env.connect(params.clone()));
+    Ok(ReconnectingConnection { env: env, params: params, settings: Settings::default(), conn: conn })
+  }
+  /// Получает текущее соединение. После переподключения возвращается уже другое соединение, поэтому настройки,
+  /// которые должны его пережить, следует устанавливать методами обертки.
+  #[inline]
+  pub fn connection(&self) -> &Connection<'e> {
+    &self.conn
+  }
+  /// Получает параметры, с которыми создаются соединения.
+  #[inline]
+  pub fn params(&self) -> &ConnectParams {
+    &self.params
+  }
+  /// Принудительно заменяет текущее соединение новым. Ошибки закрытия старого соединения игнорируются, т.к. обычно
+  /// переподключение выполняется именно тогда, когда связь с сервером уже потеряна.
+  ///
+  /// Перед заменой к новому соединению применяются все запомненные настройки.
+  ///
+  /// # Запросы к серверу (1-4)
+  /// Функция выполняет один запрос к серверу для создания нового соединения, еще один для включения буферизации
+  /// вывода `DBMS_OUTPUT`, если она была включена, и еще до двух запросов при закрытии старого, если связь с сервером
+  /// для него еще не потеряна.
+  pub fn reconnect(&mut self) -> Result<()> {
+    let conn = try!(self.env.connect(self.params.clone()));
+    try!(self.settings.apply(&conn));
+    drop(mem::replace(&mut self.conn, conn));
+    Ok(())
+  }
+  /// Устанавливает количество строк, получаемых заранее, для всех выражений, которые будут подготовлены текущим и
+  /// последующими соединениями. См. [`Connection::set_prefetch_rows()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ../struct.Connection.html#method.set_prefetch_rows
+  pub fn set_prefetch_rows(&mut self, rows: u32) {
+    self.conn.set_prefetch_rows(rows);
+    self.settings.prefetch_rows = Some(rows);
+  }
+  /// Устанавливает объем памяти под строки, получаемые заранее, для всех выражений, которые будут подготовлены текущим
+  /// и последующими соединениями. См. [`Connection::set_prefetch_memory()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ../struct.Connection.html#method.set_prefetch_memory
+  pub fn set_prefetch_memory(&mut self, bytes: u32) {
+    self.conn.set_prefetch_memory(bytes);
+    self.settings.prefetch_memory = Some(bytes);
+  }
+  /// Устанавливает размер кеша выражений текущего и последующих соединений. См.
+  /// [`Connection::set_statement_cache_size()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ../struct.Connection.html#method.set_statement_cache_size
+  pub fn set_statement_cache_size(&mut self, size: u32) -> Result<()> {
+    try!(self.conn.set_statement_cache_size(size));
+    self.settings.statement_cache_size = Some(size);
+    Ok(())
+  }
+  /// Включает буферизацию вывода `DBMS_OUTPUT` в текущей сессии и во всех сессиях, созданных при переподключении.
+  /// Строки, не полученные из буфера до потери связи, теряются. См. [`Connection::enable_output()`][1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу -- вызов процедуры `DBMS_OUTPUT.ENABLE`.
+  ///
+  /// [1]: ../struct.Connection.html#method.enable_output
+  pub fn enable_output(&mut self, buffer_size: Option<u32>) -> Result<()> {
+    try!(self.conn.enable_output(buffer_size));
+    self.settings.output = Some(buffer_size);
+    Ok(())
+  }
+  /// Выключает буферизацию вывода `DBMS_OUTPUT` в текущей сессии, после чего она больше не включается при
+  /// переподключении. См. [`Connection::disable_output()`][1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу -- вызов процедуры `DBMS_OUTPUT.DISABLE`.
+  ///
+  /// [1]: ../struct.Connection.html#method.disable_output
+  pub fn disable_output(&mut self) -> Result<()> {
+    try!(self.conn.disable_output());
+    self.settings.output = None;
+    Ok(())
+  }
+  /// Выполняет замыкание с текущим соединением. Если замыкание вернуло ошибку потери связи с сервером, переподключается
+  /// и выполняет его еще раз с новым соединением.
+  ///
+  /// # Запросы к серверу
+  /// Помимо запросов, выполняемых замыканием, при потере связи выполняется запрос для создания нового соединения.
+  pub fn run<T, F>(&mut self, mut f: F) -> Result<T>
+    where F: FnMut(&Connection) -> Result<T>
+  {
+    match f(&self.conn) {
+      Err(ref e) if e.is_connection_lost() => {},
+      res => return res,
+    }
+    try!(self.reconnect());
+    f(&self.conn)
+  }
+  /// Подготавливает выражение с указанным текстом и выполняет с ним замыкание. Если при подготовке выражения или
+  /// в замыкании произошла ошибка потери связи с сервером, переподключается, заново подготавливает выражение по его
+  /// тексту и выполняет замыкание еще раз.
+  ///
+  /// # Запросы к серверу
+  /// Помимо запросов, выполняемых замыканием, функция выполняет запрос для подготовки выражения, а при потере связи --
+  /// запросы для создания нового соединения и повторной подготовки выражения.
+  pub fn with_statement<T, F>(&mut self, sql: &str, mut f: F) -> Result<T>
+    where F: FnMut(&mut Statement) -> Result<T>
+  {
+    self.run(|conn| {
+      let mut stmt = try!(conn.prepare(sql));
+      f(&mut stmt)
+    })
+  }
+}
+
+/// Настройки соединения, запомненные оберткой для повторного применения после переподключения.
+#[derive(Clone, Debug, Default)]
+struct Settings {
+  /// Количество строк, получаемых заранее, если было установлено.
+  prefetch_rows: Option<u32>,
+  /// Объем памяти под строки, получаемые заранее, если был установлен.
+  prefetch_memory: Option<u32>,
+  /// Размер кеша выражений, если был установлен.
+  statement_cache_size: Option<u32>,
+  /// Размер буфера `DBMS_OUTPUT`, если буферизация вывода включена (`Some(None)` -- буфер без ограничения размера).
+  output: Option<Option<u32>>,
+}
+impl Settings {
+  /// Применяет запомненные настройки к новому соединению.
+  fn apply(&self, conn: &Connection) -> Result<()> {
+    if let Some(rows) = self.prefetch_rows {
+      conn.set_prefetch_rows(rows);
+    }
+    if let Some(bytes) = self.prefetch_memory {
+      conn.set_prefetch_memory(bytes);
+    }
+    if let Some(size) = self.statement_cache_size {
+      try!(conn.set_statement_cache_size(size));
+    }
+    if let Some(buffer_size) = self.output {
+      try!(conn.enable_output(buffer_size));
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,204 @@
+//! Содержит минимальный разбор текста DDL выражений, необходимый для того, чтобы узнать, какой хранимый объект
+//! был создан или перекомпилирован выражением, и получить сообщения компилятора для него.
+
+/// Хранимый объект, компилируемый сервером при выполнении DDL выражения.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Object {
+  /// Владелец объекта, если он явно указан в тексте выражения.
+  pub owner: Option<String>,
+  /// Имя объекта в том виде, в котором оно хранится в словаре данных.
+  pub name: String,
+  /// Типы объекта в том виде, в котором они указываются в столбце `TYPE` представления `ALL_ERRORS`. Обычно тип один,
+  /// но `ALTER PACKAGE/TYPE ... COMPILE` без уточнения компилирует и спецификацию, и тело.
+  pub kinds: Vec<&'static str>,
+}
+
+/// Лексема текста выражения.
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+  /// Идентификатор или ключевое слово. Идентификаторы без кавычек приводятся к верхнему регистру, т.к. именно так
+  /// они хранятся в словаре данных.
+  Ident(String),
+  /// Любой другой символ.
+  Char(char),
+}
+
+/// Разбивает начало текста выражения на лексемы, пропуская пробельные символы и комментарии. Разбор прекращается
+/// после получения `limit` лексем, т.к. имя объекта всегда находится в начале выражения.
+fn tokens(sql: &str, limit: usize) -> Vec<Token> {
+  let mut result = Vec::with_capacity(limit);
+  let mut chars = sql.chars().peekable();
+  while result.len() < limit {
+    let c = match chars.next() {
+      Some(c) => c,
+      None => break,
+    };
+    match c {
+      c if c.is_whitespace() => {},
+      '-' if chars.peek() == Some(&'-') => {
+        while let Some(c) = chars.next() {
+          if c == '\n' { break; }
+        }
+      },
+      '/' if chars.peek() == Some(&'*') => {
+        chars.next();
+        let mut prev = ' ';
+        while let Some(c) = chars.next() {
+          if prev == '*' && c == '/' { break; }
+          prev = c;
+        }
+      },
+      '"' => {
+        let ident = chars.by_ref().take_while(|c| *c != '"').collect();
+        result.push(Token::Ident(ident));
+      },
+      c if c.is_alphanumeric() || c == '_' => {
+        let mut ident: String = c.to_uppercase().collect();
+        while let Some(&c) = chars.peek() {
+          if !(c.is_alphanumeric() || c == '_' || c == '$' || c == '#') { break; }
+          ident.extend(c.to_uppercase());
+          chars.next();
+        }
+        result.push(Token::Ident(ident));
+      },
+      c => result.push(Token::Char(c)),
+    }
+  }
+  result
+}
+
+/// Определяет по тексту выражения `CREATE` или `ALTER ... COMPILE` хранимый объект, который компилирует сервер при
+/// его выполнении. Возвращает `None`, если выражение не создает и не перекомпилирует хранимый PL/SQL объект или
+/// представление.
+pub fn compiled_object(sql: &str) -> Option<Object> {
+  let tokens = tokens(sql, 12);
+  let mut iter = tokens.iter().peekable();
+  let word = |t: Option<&Token>| match t {
+    Some(&Token::Ident(ref s)) => Some(s.clone()),
+    _ => None,
+  };
+
+  let alter = match word(iter.next()) {
+    Some(ref s) if s == "CREATE" => false,
+    Some(ref s) if s == "ALTER" => true,
+    _ => return None,
+  };
+  // Модификаторы, не влияющие на тип объекта
+  loop {
+    match word(iter.peek().cloned()) {
+      Some(ref s) if ["OR", "REPLACE", "EDITIONABLE", "NONEDITIONABLE", "EDITIONING", "FORCE", "NO"].contains(&s.as_str()) => { iter.next(); },
+      _ => break,
+    }
+  }
+  let kind = match word(iter.next()) {
+    Some(ref s) if s == "PROCEDURE" => "PROCEDURE",
+    Some(ref s) if s == "FUNCTION" => "FUNCTION",
+    Some(ref s) if s == "TRIGGER" => "TRIGGER",
+    Some(ref s) if s == "LIBRARY" => "LIBRARY",
+    Some(ref s) if s == "VIEW" => "VIEW",
+    Some(ref s) if s == "PACKAGE" || s == "TYPE" => {
+      let body = match word(iter.peek().cloned()) {
+        Some(ref b) if b == "BODY" => true,
+        _ => false,
+      };
+      if body { iter.next(); }
+      match (s.as_str(), body) {
+        ("PACKAGE", false) => "PACKAGE",
+        ("PACKAGE", true ) => "PACKAGE BODY",
+        (_, false) => "TYPE",
+        (_, true ) => "TYPE BODY",
+      }
+    },
+    _ => return None,
+  };
+  let first = match word(iter.next()) {
+    Some(s) => s,
+    None => return None,
+  };
+  let (owner, name) = if iter.peek() == Some(&&Token::Char('.')) {
+    iter.next();
+    match word(iter.next()) {
+      Some(name) => (Some(first), name),
+      None => return None,
+    }
+  } else {
+    (None, first)
+  };
+  // `ALTER PACKAGE/TYPE ... COMPILE [DEBUG] BODY` перекомпилирует только тело, `SPECIFICATION` -- только
+  // спецификацию, а без уточнения -- и спецификацию, и тело
+  let kinds = match (alter, kind) {
+    (true, "PACKAGE") | (true, "TYPE") if word(iter.next()).map_or(false, |s| s == "COMPILE") => {
+      let mut part = word(iter.next());
+      if part.as_ref().map_or(false, |s| s == "DEBUG") {
+        part = word(iter.next());
+      }
+      match (kind, part.as_ref().map(String::as_str)) {
+        ("PACKAGE", Some("BODY")) => vec!["PACKAGE BODY"],
+        ("TYPE", Some("BODY")) => vec!["TYPE BODY"],
+        (kind, Some("SPECIFICATION")) => vec![kind],
+        ("PACKAGE", _) => vec!["PACKAGE", "PACKAGE BODY"],
+        (_, _) => vec!["TYPE", "TYPE BODY"],
+      }
+    },
+    (_, kind) => vec![kind],
+  };
+  Some(Object { owner: owner, name: name, kinds: kinds })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn object(owner: Option<&str>, name: &str, kinds: &[&'static str]) -> Option<Object> {
+    Some(Object { owner: owner.map(Into::into), name: name.into(), kinds: kinds.to_vec() })
+  }
+
+  #[test]
+  fn names() {
+    assert_eq!(object(None, "PROC", &["PROCEDURE"]), compiled_object("create procedure proc is begin null; end;"));
+    assert_eq!(object(None, "MyPkg", &["PACKAGE"]), compiled_object(r#"create package "MyPkg" as end;"#));
+    assert_eq!(object(Some("SCOTT"), "FN", &["FUNCTION"]), compiled_object("create function scott.fn return number is begin return 1; end;"));
+    assert_eq!(object(Some("Scott"), "My Fn", &["FUNCTION"]), compiled_object(r#"create function "Scott"."My Fn" return number"#));
+    assert_eq!(object(Some("SCOTT"), "My View", &["VIEW"]), compiled_object(r#"create or replace force view Scott . "My View" as select 1 x from dual"#));
+  }
+
+  #[test]
+  fn comments() {
+    assert_eq!(object(None, "TRG", &["TRIGGER"]), compiled_object("-- comment\ncreate -- create\n trigger trg before insert on t"));
+    assert_eq!(object(None, "LIB", &["LIBRARY"]), compiled_object("/* comment */create/**/library/* * / */lib as '/lib.so'"));
+  }
+
+  #[test]
+  fn bodies() {
+    assert_eq!(object(None, "PKG", &["PACKAGE BODY"]), compiled_object("CREATE OR REPLACE EDITIONABLE PACKAGE BODY pkg AS END;"));
+    assert_eq!(object(None, "PKG", &["PACKAGE BODY"]), compiled_object("create or replace noneditionable package body pkg as end;"));
+    assert_eq!(object(None, "T", &["TYPE"]), compiled_object("create or replace type t as object (x number);"));
+    assert_eq!(object(Some("SCOTT"), "T", &["TYPE BODY"]), compiled_object("create type body scott.t as end;"));
+  }
+
+  #[test]
+  fn alter_compile() {
+    assert_eq!(object(None, "X", &["PACKAGE", "PACKAGE BODY"]), compiled_object("alter package x compile"));
+    assert_eq!(object(None, "X", &["PACKAGE", "PACKAGE BODY"]), compiled_object("alter package x compile debug"));
+    assert_eq!(object(None, "X", &["PACKAGE", "PACKAGE BODY"]), compiled_object("alter package x compile package"));
+    assert_eq!(object(None, "X", &["PACKAGE BODY"]), compiled_object("alter package x compile body"));
+    assert_eq!(object(None, "X", &["PACKAGE BODY"]), compiled_object("ALTER PACKAGE x COMPILE DEBUG BODY"));
+    assert_eq!(object(None, "X", &["PACKAGE"]), compiled_object("alter package x compile specification"));
+    assert_eq!(object(None, "X", &["PACKAGE"]), compiled_object("alter package x compile debug specification"));
+    assert_eq!(object(Some("SCOTT"), "T", &["TYPE", "TYPE BODY"]), compiled_object("alter type scott.t compile"));
+    assert_eq!(object(Some("SCOTT"), "T", &["TYPE BODY"]), compiled_object("alter type scott.t compile body"));
+    assert_eq!(object(None, "P", &["PROCEDURE"]), compiled_object("alter procedure p compile"));
+  }
+
+  #[test]
+  fn not_matching() {
+    assert_eq!(None, compiled_object(""));
+    assert_eq!(None, compiled_object("-- create package p"));
+    assert_eq!(None, compiled_object("select 1 from dual"));
+    assert_eq!(None, compiled_object("create table t (id number)"));
+    assert_eq!(None, compiled_object("alter session set plsql_warnings = 'ENABLE:ALL'"));
+    assert_eq!(None, compiled_object("create or replace"));
+    assert_eq!(None, compiled_object("create package"));
+    assert_eq!(None, compiled_object("create package scott."));
+  }
+}
@@ -1,31 +1,44 @@
 //! Содержит определение подготовленных выражений, которые используются для эффективного исполнения запросов,
 //! и структур, связанных с ними.
+mod ddl;
 pub mod index;
+pub mod params;
 pub mod query;
 mod storage;
 
+use std::cell::RefCell;
+use std::cmp;
 use std::i32;
+use std::io::{self, Read};
+use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
+use std::rc::Rc;
+use std::slice;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use {Connection, DbResult, Result};
-use convert::{BindInfo, AsDB};
+use convert::{BindInfo, AsDB, FromDB};
+use error::{self, DbError, Diagnostic, Error};
 use types::{Type, Syntax, StatementType};
+use version::Version;
 
 use ffi::{Descriptor, Handle};// Основные типобезопасные примитивы
 use ffi::ParamHandle;// Типажи для безопасного моста к FFI
 
 use ffi::attr::AttrHolder;
-use ffi::native::{OCIBind, OCIParam, OCIStmt, OCIError};// FFI типы
-use ffi::native::{OCIParamGet, OCIStmtExecute, OCIStmtRelease, OCIStmtPrepare2, OCIStmtFetch2, OCIBindByPos, OCIBindByName, OCIBindDynamic, OCIDefineByPos};// FFI функции
-use ffi::native::bind::{BindContext, in_bind_adapter};
+use ffi::native::{OCIBind, OCIDefine, OCIParam, OCIStmt, OCIError};// FFI типы
+use ffi::native::{OCIParamGet, OCIStmtExecute, OCIStmtRelease, OCIStmtPrepare2, OCIStmtFetch2, OCIStmtGetNextResult, OCIStmtGetPieceInfo, OCIStmtSetPieceInfo, OCIBindByPos, OCIBindByName, OCIBindDynamic, OCIDefineByPos, OCIStmtGetBindInfo};// FFI функции
+use ffi::native::reset;
+use ffi::native::bind::{BindContext, OutBindContext, in_bind_adapter, null_in_bind_adapter, out_bind_adapter};
 use ffi::types::Attr;
-use ffi::types::{BindMode, DefineMode, CachingMode, ExecuteMode, FetchMode, Piece, OCIInd};
+use ffi::types::{BindMode, DefineMode, CachingMode, ExecuteMode, FetchMode, Piece, ReleaseMode};
 
 use self::index::BindIndex;
-use self::storage::DefineInfo;
-use self::query::RowSet;
+use self::params::ToParams;
+use self::storage::{BindArray, BindTable, DefineInfo, InBind, OutBind, PIECE_SIZE, out_size};
+use self::query::{RowSet, ScrollableRowSet};
 
 //-------------------------------------------------------------------------------------------------
 fn param_get<'d, T: ParamHandle>(handle: *const T, pos: u32, err: &Handle<OCIError>) -> DbResult<Descriptor<'d, OCIParam>> {
@@ -40,6 +53,16 @@ fn param_get<'d, T: ParamHandle>(handle: *const T, pos: u32, err: &Handle<OCIErr
   Descriptor::from_ptr(res, desc as *const OCIParam, err)
 }
 
+/// Счетчик для присвоения выражениям уникальных номеров, см. `Statement::id`.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Получает ключ, по которому массив, связанный методом `bind_array()`, или входное значение заменяются при повторном
+/// связывании той же переменной. Имена переменных в Oracle не чувствительны к регистру.
+#[inline]
+fn array_key(index: BindIndex) -> String {
+  index.to_string().to_uppercase()
+}
+
 //-------------------------------------------------------------------------------------------------
 /// Структура для представления колонки базы данных из списка выбора
 #[derive(Debug)]
@@ -83,6 +106,148 @@ impl Column {
     })
   }
 }
+/// Ошибка выполнения одной из итераций при [пакетном выполнении][1] выражения.
+///
+/// [1]: ./struct.Statement.html#method.execute_batch
+#[derive(Debug)]
+pub struct BatchError {
+  /// Номер итерации (нумерация с 0), т.е. индекс элемента в связанных массивах, при выполнении которой возникла ошибка.
+  pub offset: usize,
+  /// Код и сообщение об ошибке.
+  pub info: error::Info,
+}
+/// Результат [пакетного выполнения][1] выражения.
+///
+/// [1]: ./struct.Statement.html#method.execute_batch
+#[derive(Debug)]
+pub struct BatchResult {
+  /// Количество строк, затронутых каждой итерацией. Для итераций, завершившихся ошибкой, равно `0`.
+  ///
+  /// Количества строк по итерациям поддерживаются клиентом и сервером, начиная с версии 12.1. При более старой версии
+  /// клиента или сервера список пуст, а общее количество строк по-прежнему доступно через метод [`total()`][1].
+  ///
+  /// [1]: #method.total
+  pub row_counts: Vec<u64>,
+  /// Ошибки итераций, завершившихся неудачно, в порядке возрастания номеров итераций.
+  pub errors: Vec<BatchError>,
+  /// Общее количество строк, затронутых всеми итерациями.
+  total: u64,
+}
+impl BatchResult {
+  /// Возвращает `true`, если все итерации выполнились без ошибок.
+  #[inline]
+  pub fn is_ok(&self) -> bool {
+    self.errors.is_empty()
+  }
+  /// Общее количество строк, затронутых всеми итерациями.
+  #[inline]
+  pub fn total(&self) -> u64 {
+    self.total
+  }
+}
+/// Описатель выходной (`OUT` или `IN OUT`) переменной, [связанной][1] с выражением. Значение переменной, записанное
+/// сервером, можно получить после выполнения выражения методом [`get_out()`][2] того выражения, с которым она связана.
+///
+/// [1]: ./struct.Statement.html#method.bind_out
+/// [2]: ./struct.Statement.html#method.get_out
+#[derive(Debug)]
+pub struct OutParam<T> {
+  /// Номер выражения, с которым связана переменная.
+  stmt: usize,
+  /// Индекс буфера переменной в списке выходных переменных выражения.
+  index: usize,
+  /// Маркер типа значения переменной.
+  _phantom: PhantomData<T>,
+}
+/// Описатель выходного (`OUT` или `IN OUT`) PL/SQL массива, [связанного][1] с выражением. Элементы массива, записанные
+/// сервером, можно получить после выполнения выражения методом [`get_table()`][2] того выражения, с которым он связан.
+///
+/// [1]: ./struct.Statement.html#method.bind_table_out
+/// [2]: ./struct.Statement.html#method.get_table
+#[derive(Debug)]
+pub struct TableParam<T> {
+  /// Номер выражения, с которым связан массив.
+  stmt: usize,
+  /// Индекс буфера массива в списке PL/SQL массивов выражения.
+  index: usize,
+  /// Маркер типа элементов массива.
+  _phantom: PhantomData<T>,
+}
+/// Описатель переменной из предложения `RETURNING ... INTO` DML выражения, [связанной][1] с выражением. Значения,
+/// возвращенные сервером, можно получить после выполнения выражения методом [`get_returning()`][2] того выражения,
+/// с которым она связана.
+///
+/// [1]: ./struct.Statement.html#method.bind_returning
+/// [2]: ./struct.Statement.html#method.get_returning
+#[derive(Debug)]
+pub struct ReturningParam<T> {
+  /// Номер выражения, с которым связана переменная.
+  stmt: usize,
+  /// Индекс буфера переменной в списке переменных `RETURNING ... INTO` выражения.
+  index: usize,
+  /// Маркер типа значения переменной.
+  _phantom: PhantomData<T>,
+}
+/// Информация о переменной связывания (placeholder-е), найденной в тексте выражения методом [`bind_names()`][1].
+///
+/// [1]: ./struct.Statement.html#method.bind_names
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindName {
+  /// Имя переменной без начального двоеточия, в том виде, в котором его возвращает сервер (в верхнем регистре).
+  pub name: String,
+  /// Порядковый номер (нумерация с 0) первого вхождения переменной в выражение. Может использоваться в качестве
+  /// индекса при связывании.
+  pub pos: usize,
+  /// Признак того, что переменная встречается в тексте выражения более одного раза.
+  pub duplicated: bool,
+  /// Признак того, что с переменной уже связано значение.
+  pub bound: bool,
+}
+/// Итератор по неявным наборам результатов, возвращенным PL/SQL блоком вызовами `DBMS_SQL.RETURN_RESULT`.
+/// Создается методом [`Statement::implicit_results()`][1].
+///
+/// [1]: ./struct.Statement.html#method.implicit_results
+#[derive(Debug)]
+pub struct ImplicitResults<'s> {
+  /// Выражение, вернувшее наборы результатов.
+  stmt: &'s Statement<'s, 's>,
+}
+impl<'s> Iterator for ImplicitResults<'s> {
+  type Item = Result<Statement<'s, 'static>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let mut result = ptr::null_mut();
+    let mut rtype = 0;
+    let res = unsafe {
+      OCIStmtGetNextResult(
+        self.stmt.native as *mut OCIStmt,
+        self.stmt.error().native_mut(),
+        &mut result,
+        &mut rtype,
+        0
+      )
+    };
+    match self.stmt.error().check(res) {
+      // Хендлом неявного результата владеет родительское выражение
+      Ok(_) => Some(Ok(Statement::from_cursor(self.stmt.conn, result as *mut OCIStmt))),
+      Err(DbError::NoData) => None,
+      Err(e) => Some(Err(e.into())),
+    }
+  }
+}
+/// Описатель выходной переменной типа `SYS_REFCURSOR`, [связанной][1] с выражением. Открытый сервером курсор можно
+/// получить после выполнения выражения методом [`get_cursor()`][2] того выражения, с которым она связана.
+///
+/// [1]: ./struct.Statement.html#method.bind_cursor
+/// [2]: ./struct.Statement.html#method.get_cursor
+#[derive(Debug)]
+pub struct CursorParam {
+  /// Номер выражения, с которым связана переменная.
+  stmt: usize,
+  /// Индекс буфера переменной в списке выходных переменных выражения.
+  index: usize,
+}
+
 //-------------------------------------------------------------------------------------------------
 /// Подготовленное выражение.
 #[derive(Debug)]
@@ -91,11 +256,50 @@ pub struct Statement<'conn, 'key> {
   conn: &'conn Connection<'conn>,
   /// Внутренний указатель оракла на подготовленное выражение
   native: *const OCIStmt,
+  /// Уникальный номер выражения, которым помечаются описатели связанных с ним выходных переменных, чтобы их нельзя
+  /// было использовать с другим выражением. В отличие от адреса хендла, не может повториться после закрытия выражения.
+  id: usize,
   /// Ключ для кеширования выражения
   key: Option<&'key str>,
   /// Список с информацией о динамическом связывании переменных: каждая связанная переменная представляется
-  /// одной записью в данном списке
-  binds: Vec<BindContext<'conn>>,
+  /// одной записью в данном списке. Адреса записей переданы в OCI, поэтому они хранятся в куче, чтобы не меняться
+  /// при росте списка
+  binds: Vec<Box<BindContext<'conn>>>,
+  /// Ошибка чтения из читателя, связанного методом `bind_reader()` или `bind_text_reader()`, из-за которой было
+  /// прервано выполнение выражения. Разделяется с функциями, читающими данные
+  read_error: Rc<RefCell<Option<io::Error>>>,
+  /// Буферы с данными массивов, связанных с выражением для массивного выполнения, вместе с ключами переменных, с
+  /// которыми они связаны (см. `array_key()`). Каждая переменная, связанная с массивом, представляется одной записью
+  /// в данном списке
+  arrays: Vec<(String, BindArray)>,
+  /// Ключи переменных (см. `array_key()`), связанных с буферами из одного элемента: входных и выходных переменных,
+  /// курсоров и PL/SQL массивов. При массивном выполнении сервер прочитал бы за пределами таких буферов, поэтому оно
+  /// запрещено, пока с выражением связана хотя бы одна такая переменная
+  scalars: Vec<String>,
+  /// Индикаторы `NULL`-ов и копии значений входных переменных вместе с ключами переменных, с которыми они связаны
+  /// (см. `array_key()`). Каждая входная переменная представляется одной записью в данном списке
+  ins: Vec<(String, InBind)>,
+  /// Буферы выходных переменных, в которые сервер записывает значения при выполнении выражения. Каждая выходная
+  /// переменная представляется одной записью в данном списке
+  outs: Vec<OutBind>,
+  /// Буферы переменных из предложения `RETURNING ... INTO`, в которые сервер записывает значения при выполнении
+  /// выражения. Каждая такая переменная представляется одной записью в данном списке
+  returns: Vec<Box<OutBindContext>>,
+  /// Буферы PL/SQL массивов, связанных с выражением. Каждый связанный массив представляется одной записью в данном
+  /// списке
+  tables: Vec<BindTable>,
+  /// Количество строк, извлекаемых из базы за один запрос к серверу при итерации по результатам `SELECT`-выражения
+  fetch_size: u32,
+  /// Признак того, что выражение является курсором, открытым сервером (`SYS_REFCURSOR` или `CURSOR(...)`), а не
+  /// подготовленным приложением. Такое выражение уже выполнено, а его хендлом владеет породивший его объект.
+  cursor: bool,
+  /// Признак того, что при закрытии выражение должно быть удалено из кеша выражений, а не помещено в него.
+  evict: bool,
+  /// Предупреждения компилятора для хранимого объекта, созданного или перекомпилированного при последнем выполнении
+  /// выражения, см. [`compile_warnings()`][1].
+  ///
+  /// [1]: #method.compile_warnings
+  warnings: RefCell<Vec<Diagnostic>>,
 }
 impl<'conn, 'key> Statement<'conn, 'key> {
   /// Получает хендл для записи ошибок во время общения с базой данных. Хендл берется из соединения, которое породило
@@ -105,6 +309,27 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   fn error(&self) -> &Handle<OCIError> {
     self.conn.error()
   }
+  /// Создает выражение для курсора, открытого сервером. Хендлом курсора владеет породивший его объект.
+  fn from_cursor(conn: &'conn Connection, native: *mut OCIStmt) -> Statement<'conn, 'static> {
+    Statement {
+      conn: conn,
+      native: native,
+      id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+      key: None,
+      binds: Vec::new(),
+      read_error: Rc::new(RefCell::new(None)),
+      arrays: Vec::new(),
+      scalars: Vec::new(),
+      ins: Vec::new(),
+      outs: Vec::new(),
+      returns: Vec::new(),
+      tables: Vec::new(),
+      fetch_size: 1,
+      cursor: true,
+      evict: false,
+      warnings: RefCell::new(Vec::new()),
+    }
+  }
   /// # Параметры
   /// - `count`:
   ///   * Для `select` выражений это количество строк, которые нужно извлечь prefetch-ем, уже в момент выполнения
@@ -114,7 +339,12 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   ///     нужно использовать при выполнении данной операции
   /// - `offset`:
   ///   Смещение с буфере со связанными переменными, с которого необходимо начать выполнение 
-  fn execute_impl(&self, count: u32, offset: u32, mode: ExecuteMode) -> DbResult<()> {
+  /// - `mode`:
+  ///   Битовая маска из одного или нескольких режимов `ExecuteMode`.
+  fn execute_impl(&self, count: u32, offset: u32, mode: u32) -> Result<()> {
+    for ctx in &self.returns {
+      ctx.clear();
+    }
     let res = unsafe {
       OCIStmtExecute(
         self.conn.context.native_mut(),
@@ -124,10 +354,15 @@ impl<'conn, 'key> Statement<'conn, 'key> {
         offset,
         ptr::null(),
         ptr::null_mut(),
-        mode as u32
+        mode
       )
     };
-    return self.error().check(res);
+    let res = self.reset_if_cancelled(self.error().check(res));
+    // OCI прерывает выполнение при ошибке читателя анонимной ошибкой ORA-24343, поэтому возвращаем саму ошибку чтения
+    if let Some(e) = self.read_error.borrow_mut().take() {
+      return Err(Error::Io(e));
+    }
+    res.map_err(Into::into)
   }
   /// Извлекает из текущего выражения данные, которые в нем имеются после выполнения `select`-а.
   ///
@@ -147,7 +382,84 @@ impl<'conn, 'key> Statement<'conn, 'key> {
         0 // Неясно, что такое
       )
     };
-    return self.error().check(res);
+    return self.reset_if_cancelled(self.error().check(res));
+  }
+  /// Если выполнение или извлечение было прервано из другого потока вызовом [`CancelHandle::cancel()`][1]
+  /// (`ORA-01013`), сбрасывает состояние протокола соединения, чтобы им можно было продолжать пользоваться.
+  /// Результат возвращается без изменений.
+  ///
+  /// [1]: ../cancel/struct.CancelHandle.html#method.cancel
+  fn reset_if_cancelled(&self, res: DbResult<()>) -> DbResult<()> {
+    if let Err(DbError::Fault(error::Info { code: 1013, .. })) = res {
+      // Ошибка сброса не интересна, вызывающему важна причина прерывания
+      let _ = reset(&self.conn.context, self.error());
+    }
+    res
+  }
+  /// Извлекает строки, как и [`fetch()`][1], но при этом предоставляет серверу буферы для значений столбцов,
+  /// связанных в режиме `DynamicFetch`, по мере того, как он их запрашивает. Значения таких столбцов накапливаются
+  /// в хранилищах соответствующих столбцов.
+  ///
+  /// [1]: #method.fetch
+  fn fetch_pieces(&self, count: u32, mode: FetchMode, index: i32, data: &mut [DefineInfo]) -> Result<()> {
+    let mut res = self.fetch(count, mode, index);
+    while let Err(DbError::NeedData) = res {
+      let (handle, htype, piece) = try!(self.piece_info());
+      let buf = match data.iter_mut().find(|d| d.handle() == handle as *mut OCIDefine) {
+        Some(buf) => buf,
+        None => return Err(DbError::InvalidHandle.into()),
+      };
+      let (ptr, alen) = buf.next_piece();
+      try!(self.set_piece_info(handle, htype, ptr, alen, piece, buf.is_null_mut(), buf.ret_codes_mut()));
+      res = self.fetch(count, mode, index);
+    }
+    for buf in data.iter_mut() {
+      buf.finish_pieces();
+    }
+    res.map_err(Into::into)
+  }
+  /// Получает информацию о том, для какого хендла и какую часть значения запрашивает сервер при операциях по частям.
+  fn piece_info(&self) -> Result<(*mut c_void, u32, Piece)> {
+    let mut handle = ptr::null_mut();
+    let mut htype = 0;
+    let mut in_out = 0;
+    let mut iter = 0;
+    let mut idx = 0;
+    let mut piece = 0;
+    let res = unsafe {
+      OCIStmtGetPieceInfo(
+        self.native,
+        self.error().native_mut(),
+        &mut handle, &mut htype,
+        &mut in_out,
+        &mut iter, &mut idx,
+        &mut piece
+      )
+    };
+    try!(self.error().check(res));
+    let piece = match piece {
+      0 => Piece::One,
+      1 => Piece::First,
+      2 => Piece::Next,
+      3 => Piece::Last,
+      // Других значений OCI не возвращает, но и получить из них перечисление нельзя
+      piece => return Err(Error::UnknownPiece(piece)),
+    };
+    Ok((handle, htype, piece))
+  }
+  /// Предоставляет серверу буфер для запрошенной им части значения при операциях по частям.
+  fn set_piece_info(&self, handle: *mut c_void, htype: u32, buf: *mut c_void, alen: *mut u32, piece: Piece, is_null: *mut c_void, ret_code: *mut u16) -> DbResult<()> {
+    let res = unsafe {
+      OCIStmtSetPieceInfo(
+        handle, htype,
+        self.error().native_mut(),
+        buf, alen,
+        piece as u8,
+        is_null,
+        ret_code
+      )
+    };
+    self.error().check(res)
   }
   /// # Парaметры
   /// - `pos`:
@@ -155,14 +467,16 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   ///   параметра должно привязываться отдельно и может иметь разное значение в каждой привязке.
   /// - `info`:
   ///   Данные для связывания.
-  fn bind_by_pos(&self, pos: u32, info: BindInfo, mode: BindMode) -> DbResult<*mut OCIBind> {
+  /// - `is_null`:
+  ///   Указатель на индикатор `NULL`-а, принадлежащий выражению, или нулевой указатель при динамическом связывании.
+  fn bind_by_pos(&self, pos: u32, info: BindInfo, is_null: *mut c_void, mode: BindMode) -> DbResult<*mut OCIBind> {
     let mut handle = ptr::null_mut();
-    let (is_null, size) = if mode == BindMode::DataAtExec {
+    let size = if mode == BindMode::DataAtExec {
       // В случае динамического предоставления данных в качестве длины указывается максимально
       // возможный общий предоставляемый размер данных. Даем по максимуму.
-      (ptr::null_mut(), i32::MAX)
+      i32::MAX
     } else {
-      (&info.is_null as *const _ as *mut OCIInd, info.size as i32)
+      info.size as i32
     };
     let res = unsafe {
       OCIBindByPos(
@@ -173,7 +487,7 @@ impl<'conn, 'key> Statement<'conn, 'key> {
         pos + 1,
         // Указатель на буфер с данными, его размер и тип
         info.ptr as *mut c_void, size, info.ty as u16,
-        is_null as *mut c_void,// Массив индикаторов (null/не null)
+        is_null,// Массив индикаторов (null/не null)
         ptr::null_mut(),// Массив длин для каждого значения
         ptr::null_mut(),// Массив для column-level return codes
 
@@ -183,14 +497,14 @@ impl<'conn, 'key> Statement<'conn, 'key> {
     try!(self.error().check(res));
     Ok(handle)
   }
-  fn bind_by_name(&self, placeholder: &str, info: BindInfo, mode: BindMode) -> DbResult<*mut OCIBind> {
+  fn bind_by_name(&self, placeholder: &str, info: BindInfo, is_null: *mut c_void, mode: BindMode) -> DbResult<*mut OCIBind> {
     let mut handle = ptr::null_mut();
-    let (is_null, size) = if mode == BindMode::DataAtExec {
+    let size = if mode == BindMode::DataAtExec {
       // В случае динамического предоставления данных в качестве длины указывается максимально
       // возможный общий предоставляемый размер данных. Даем по максимуму.
-      (ptr::null_mut(), i32::MAX)
+      i32::MAX
     } else {
-      (&info.is_null as *const _ as *mut OCIInd, info.size as i32)
+      info.size as i32
     };
     let res = unsafe {
       OCIBindByName(
@@ -200,7 +514,7 @@ impl<'conn, 'key> Statement<'conn, 'key> {
         placeholder.as_ptr(), placeholder.len() as i32,
         // Указатель на буфер с данными, его размер и тип
         info.ptr as *mut c_void, size, info.ty as u16,
-        is_null as *mut c_void,// Массив индикаторов (null/не null)
+        is_null,// Массив индикаторов (null/не null)
         ptr::null_mut(),// Массив длин для каждого значения
         ptr::null_mut(),// Массив для column-level return codes
 
@@ -224,10 +538,10 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   fn bind_dynamic<F>(&mut self, handle: *mut OCIBind, supplier: F) -> DbResult<()>
     where F: FnMut(&mut OCIBind, &mut Vec<u8>, u32, u32, Piece) -> (bool, Piece, bool) + 'conn
   {
-    self.binds.push(BindContext::new(supplier));
+    self.binds.push(Box::new(BindContext::new(supplier)));
     let error = self.error().native_mut();
     let res = unsafe {
-      let ctx = self.binds.last_mut().unwrap();
+      let ctx: &mut BindContext = self.binds.last_mut().unwrap();
       OCIBindDynamic(
         handle,
         error,
@@ -237,11 +551,88 @@ impl<'conn, 'key> Statement<'conn, 'key> {
     };
     self.error().check(res)
   }
+  /// Связывает с переменной буфер (или массив буферов одинакового размера), находящийся в памяти приложения.
+  /// Используется для массивного выполнения выражения, когда при `i`-ой итерации выполнения будет использован
+  /// `i`-ый элемент массива, а также для выходных переменных, в буферы которых сервер запишет значения.
+  ///
+  /// # Параметры
+  /// - `index`:
+  ///   Порядковый номер (нумерация с 0) или символьное имя переменной в запросе.
+  /// - `ptr`, `size`, `ty`:
+  ///   Указатель на буфер с данными, размер одного элемента в нем и тип данных.
+  /// - `is_null`:
+  ///   Указатель на массив индикаторов `NULL`-ов для каждого элемента.
+  /// - `sizes`:
+  ///   Указатель на массив реальных длин для каждого элемента.
+  /// - `max_len`, `cur_len`:
+  ///   Максимальное количество элементов и указатель на текущее количество элементов, если переменная является
+  ///   PL/SQL массивом. Для остальных переменных должны быть `0` и нулевой указатель.
+  fn bind_buffer(&self, index: BindIndex, ptr: *mut c_void, size: i32, ty: Type, is_null: *mut c_void, sizes: *mut u16, max_len: u32, cur_len: *mut u32) -> DbResult<*mut OCIBind> {
+    let mut handle = ptr::null_mut();
+    let res = unsafe {
+      match index {
+        BindIndex::Name(name) => OCIBindByName(
+          self.native as *mut OCIStmt,
+          &mut handle,
+          self.error().native_mut(),
+          name.as_ptr(), name.len() as i32,
+          ptr, size, ty as u16,
+          is_null,
+          sizes,
+          ptr::null_mut(),// Массив для column-level return codes
+
+          // Для массивов, не являющихся PL/SQL массивами, количество элементов задается при выполнении
+          max_len, cur_len, BindMode::default() as u32
+        ),
+        BindIndex::Index(pos) => OCIBindByPos(
+          self.native as *mut OCIStmt,
+          &mut handle,
+          self.error().native_mut(),
+          // В API оракла нумерация с 1, мы же придерживаемся традиционной с 0
+          pos as u32 + 1,
+          ptr, size, ty as u16,
+          is_null,
+          sizes,
+          ptr::null_mut(),// Массив для column-level return codes
+
+          // Для массивов, не являющихся PL/SQL массивами, количество элементов задается при выполнении
+          max_len, cur_len, BindMode::default() as u32
+        ),
+      }
+    };
+    try!(self.error().check(res));
+    Ok(handle)
+  }
   #[inline]
-  fn bind_value(&self, index: BindIndex, info: BindInfo, mode: BindMode) -> DbResult<*mut OCIBind> {
+  fn bind_value(&self, index: BindIndex, info: BindInfo, is_null: *mut c_void, mode: BindMode) -> DbResult<*mut OCIBind> {
     match index {
-      BindIndex::Name(name) => self.bind_by_name(name, info, mode),
-      BindIndex::Index(pos) => self.bind_by_pos(pos as u32, info, mode),
+      BindIndex::Name(name) => self.bind_by_name(name, info, is_null, mode),
+      BindIndex::Index(pos) => self.bind_by_pos(pos as u32, info, is_null, mode),
+    }
+  }
+  /// Связывает с переменной входное значение, индикатор `NULL`-а которого (а если `copy` равен `true`, то и само
+  /// значение) хранится в памяти выражения. Повторное связывание той же переменной заменяет ранее сохраненное значение.
+  fn bind_in(&mut self, index: BindIndex, mut info: BindInfo, copy: bool) -> DbResult<()> {
+    let mut value = if copy { InBind::copied(&info) } else { InBind::borrowed(&info) };
+    if copy {
+      info.ptr = value.as_ptr();
+    }
+    try!(self.bind_value(index, info, value.is_null_mut(), BindMode::default()));
+    // Старое значение освобождается только после того, как переменная перестала на него ссылаться
+    let key = array_key(index);
+    self.add_scalar(&key);
+    match self.ins.iter().position(|&(ref k, _)| *k == key) {
+      Some(i) => self.ins[i].1 = value,
+      None => self.ins.push((key, value)),
+    }
+    Ok(())
+  }
+  /// Запоминает, что переменная с указанным ключом связана с буфером из одного элемента. Массив, ранее связанный с
+  /// той же переменной, больше не используется сервером и освобождается.
+  fn add_scalar(&mut self, key: &str) {
+    self.arrays.retain(|&(ref k, _)| k != key);
+    if !self.scalars.iter().any(|k| k == key) {
+      self.scalars.push(key.to_owned());
     }
   }
   /// Ассоциирует с выражением адреса буферов, в которые извлечь данные.
@@ -252,30 +643,30 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   /// - `dty`:
   ///   Тип данных, которые нужно извлечь
   /// - `buf`:
-  ///   Буфер, в который будет записана выходная информация.
-  /// - `ind`:
-  ///   Переменная, в которую будет записан признак того, что в столбце содержится `NULL`.
-  /// - `out_size`:
-  ///   Количество байт, записанное в буфер. Не превышает его длину
+  ///   Буферы, в которые будут записаны значения, признаки `NULL`-ов и реальные размеры значений для каждой
+  ///   извлекаемой строки.
   fn define(&self, pos: u32, dty: Type, buf: &mut DefineInfo, mode: DefineMode) -> DbResult<()> {
+    // Хендл принадлежит выражению и освобождается вместе с ним, запоминаем его лишь для того, чтобы узнавать
+    // столбец при извлечении значений по частям
+    let mut handle = ptr::null_mut();
     let res = unsafe {
       OCIDefineByPos(
         self.native as *mut OCIStmt,
-        //TODO: Чтобы управлять временем жизни хендла, нужно передать корректный хендл, но тогда его придется
-        // самому закрывать. Пока нам это не нужно
-        &mut ptr::null_mut(),
+        &mut handle,
         self.error().native_mut(),
         // В API оракла нумерация с 1, мы же придерживаемся традиционной с 0
         pos + 1,
         // Указатель на данные для размещения результата, его размер и тип
         buf.as_ptr(), buf.capacity(), dty as u16,
-        &mut buf.is_null as *mut i16 as *mut c_void,// Массив индикаторов (null/не null)
-        buf.size_mut(),// Массив длин для каждого значения, которое извлекли из базы
-        &mut buf.ret_code,// Массив для column-level return codes
+        buf.is_null_mut(),// Массив индикаторов (null/не null)
+        buf.sizes_mut(),// Массив длин для каждого значения, которое извлекли из базы
+        buf.ret_codes_mut(),// Массив для column-level return codes
         mode as u32
       )
     };
-    self.error().check(res)
+    try!(self.error().check(res));
+    buf.set_handle(handle);
+    Ok(())
   }
   /// Получает количество столбцов, извлеченный в `SELECT`-выражении. Необходимо вызывать после выполнения `SELECT`-запроса,
   /// т.к. до этого момента? или в случае выполнения не `SELECT`-запроса, эта информация недоступна.
@@ -289,6 +680,16 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   fn row_count(&self) -> DbResult<u64> {
     self.get_(Attr::RowCount, self.error())
   }
+  /// Получает количество строк, извлеченных последним вызовом fetch для `SELECT` запроса.
+  #[inline]
+  fn rows_fetched(&self) -> DbResult<u32> {
+    self.get_(Attr::RowFetched, self.error())
+  }
+  /// Получает номер строки (нумерация с 1), на которой находится прокручиваемый курсор.
+  #[inline]
+  fn current_position(&self) -> DbResult<u32> {
+    self.get_(Attr::CurrentPosition, self.error())
+  }
   /// Получает дескриптор с описанием столбца в полученном списке извлеченных `SELECT`-ом столбцов для указанного столбца.
   ///
   /// # Параметры
@@ -313,6 +714,247 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   pub fn connection(&self) -> &Connection {
     self.conn
   }
+  /// Получает количество строк, извлекаемых из базы за один запрос к серверу при итерации по результатам
+  /// `SELECT`-выражения. По умолчанию равно `1`.
+  #[inline]
+  pub fn fetch_size(&self) -> u32 {
+    self.fetch_size
+  }
+  /// Устанавливает количество строк, извлекаемых из базы за один запрос к серверу при итерации по результатам
+  /// `SELECT`-выражения. Под значения указанного количества строк для каждой колонки выделяются буферы, поэтому
+  /// для выборок с широкими строками не стоит задавать слишком большое значение. Значение `0` трактуется как `1`.
+  ///
+  /// Новое значение вступает в силу для наборов результатов, полученных после его установки.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  #[inline]
+  pub fn set_fetch_size(&mut self, size: u32) {
+    self.fetch_size = cmp::max(size, 1);
+  }
+  /// Получает количество строк, которые сервер вернет сразу при выполнении запроса и при каждом извлечении порции
+  /// строк. Подробнее см. [`set_prefetch_rows()`][1].
+  ///
+  /// # OCI вызовы
+  /// Получает атрибут `OCI_ATTR_PREFETCH_ROWS` выражения OCI-вызовом [`OCIAttrGet()`][2].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.set_prefetch_rows
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  #[inline]
+  pub fn prefetch_rows(&self) -> Result<u32> {
+    // Attr::Position имеет то же значение, что и OCI_ATTR_PREFETCH_ROWS, см. `set_prefetch_rows()`
+    self.get_(Attr::Position, self.error()).map_err(Into::into)
+  }
+  /// Устанавливает количество строк, которые сервер вернет сразу при выполнении запроса и при каждом извлечении
+  /// порции строк, не дожидаясь явного запроса на их получение. Такие строки буферизуются клиентской библиотекой
+  /// Oracle, поэтому для выборок, возвращающих лишь несколько строк, выполнение и извлечение всех строк требует
+  /// всего одного запроса к серверу, даже если [размер порции][1] равен `1`.
+  ///
+  /// По умолчанию используется значение, установленное методом [`Connection::set_prefetch_rows()`][2], либо `1`,
+  /// если оно не установлено. Значение `0` отключает получение строк заранее.
+  ///
+  /// # OCI вызовы
+  /// Устанавливает атрибут `OCI_ATTR_PREFETCH_ROWS` выражения OCI-вызовом [`OCIAttrSet()`][3].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.set_fetch_size
+  /// [2]: ../struct.Connection.html#method.set_prefetch_rows
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17131
+  pub fn set_prefetch_rows(&mut self, rows: u32) -> Result<()> {
+    let err = self.conn.error();
+    //FIXME: Атрибуты PrefetchRows и Position имеют одинаковое представление в C-коде (11), но в Rust-е наличие перечислений с одним значением
+    // запрещено.
+    self.set_(rows, Attr::Position, err).map_err(Into::into)
+  }
+  /// Получает объем памяти в байтах, который клиентская библиотека Oracle может выделить под строки, получаемые
+  /// заранее. Подробнее см. [`set_prefetch_memory()`][1].
+  ///
+  /// # OCI вызовы
+  /// Получает атрибут `OCI_ATTR_PREFETCH_MEMORY` выражения OCI-вызовом [`OCIAttrGet()`][2].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.set_prefetch_memory
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  #[inline]
+  pub fn prefetch_memory(&self) -> Result<u32> {
+    self.get_(Attr::PrefetchMemory, self.error()).map_err(Into::into)
+  }
+  /// Устанавливает объем памяти в байтах, который клиентская библиотека Oracle может выделить под строки, получаемые
+  /// заранее, не дожидаясь явного запроса на их получение. Если установлено и это ограничение, и [количество строк][1],
+  /// то строк будет получено столько, чтобы не превысить ни одно из ограничений. Значение `0` снимает ограничение
+  /// по памяти.
+  ///
+  /// По умолчанию используется значение, установленное методом [`Connection::set_prefetch_memory()`][2], либо `0`,
+  /// если оно не установлено.
+  ///
+  /// # OCI вызовы
+  /// Устанавливает атрибут `OCI_ATTR_PREFETCH_MEMORY` выражения OCI-вызовом [`OCIAttrSet()`][3].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.set_prefetch_rows
+  /// [2]: ../struct.Connection.html#method.set_prefetch_memory
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17131
+  pub fn set_prefetch_memory(&mut self, bytes: u32) -> Result<()> {
+    let err = self.conn.error();
+    self.set_(bytes, Attr::PrefetchMemory, err).map_err(Into::into)
+  }
+  /// Получает описание столбцов, которые вернет `SELECT`-выражение, не выполняя его. Для остальных видов выражений
+  /// возвращает пустой список.
+  ///
+  /// Если сервер не смог разобрать выражение, возвращает ошибку [`Error::Parse`][1] с указанием места в тексте
+  /// выражения, где обнаружена ошибка.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let stmt = conn.prepare("select 1 as one, 'two' as two from dual").unwrap();
+  /// let columns = stmt.describe().unwrap();
+  /// assert_eq!(vec!["ONE", "TWO"], columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>());
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtExecute()`][2] в режиме `OCI_DESCRIBE_ONLY`, затем вызовы [`OCIParamGet()`][3]
+  /// и [`OCIAttrGet()`][4] для получения описания каждого столбца.
+  ///
+  /// # Запросы к серверу (1)
+  /// Выполняется один запрос к серверу для разбора и описания выражения.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.Parse
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17136
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  pub fn describe(&self) -> Result<Vec<Column>> {
+    try!(self.check_parse(self.execute_impl(0, 0, ExecuteMode::DescribeOnly as u32)));
+    self.columns()
+  }
+  /// Проверяет синтаксис и семантику выражения на сервере, не выполняя его.
+  ///
+  /// Если сервер не смог разобрать выражение, возвращает ошибку [`Error::Parse`][1] с указанием места в тексте
+  /// выражения, где обнаружена ошибка.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::error::Error;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let stmt = conn.prepare("select * form dual").unwrap();
+  /// match stmt.validate() {
+  ///   Err(Error::Parse { offset, info }) => println!("ORA-{:05} at {}: {}", info.code, offset, info.message),
+  ///   r => panic!("Unexpected result: {:?}", r),
+  /// }
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtExecute()`][2] в режиме `OCI_PARSE_ONLY`. При ошибке разбора смещение ошибки
+  /// получается вызовом [`OCIAttrGet()`][3].
+  ///
+  /// # Запросы к серверу (1)
+  /// Выполняется один запрос к серверу для разбора выражения.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.Parse
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  pub fn validate(&self) -> Result<()> {
+    self.check_parse(self.execute_impl(0, 0, ExecuteMode::ParseOnly as u32))
+  }
+  /// Преобразует ошибку, полученную при разборе выражения сервером, в ошибку [`Error::Parse`][1], дополняя ее
+  /// смещением в тексте выражения, в котором сервер обнаружил ошибку. Смещение `0` является корректным и означает
+  /// ошибку в первой лексеме выражения. Потеря соединения и отмена запроса (`ORA-01013`) не являются ошибками разбора
+  /// и возвращаются без изменений, как и исходная ошибка, если смещение получить не удалось.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.Parse
+  fn check_parse(&self, res: Result<()>) -> Result<()> {
+    match res {
+      Err(ref e) if e.is_connection_lost() => res,
+      Err(Error::Db(DbError::Fault(info))) if info.code != 1013 => {
+        match self.get_::<u16>(Attr::ParseErrorOffset, self.error()) {
+          Ok(offset) => Err(Error::Parse { offset: offset as usize, info: info }),
+          Err(_) => Err(Error::Db(DbError::Fault(info))),
+        }
+      },
+      res => res,
+    }
+  }
+  /// Преобразует ошибку, полученную при выполнении выражения, в ошибку [`Error::Compile`][1], если выражение создало
+  /// или перекомпилировало хранимый объект с ошибками (`ORA-24344`), дополняя ее сообщениями компилятора для этого
+  /// объекта. Если объект определить не удалось или для него нет сообщений компилятора, ошибка возвращается без
+  /// изменений.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.Compile
+  fn check_compile(&self, err: Error) -> Result<()> {
+    let compiled = match err {
+      Error::Db(DbError::Info(ref infos)) => infos.iter().any(|i| i.code == 24344),
+      _ => false,
+    };
+    if !compiled {
+      return Err(err);
+    }
+    let object = match self.get_str(Attr::Statement, self.error()).ok().and_then(|sql| ddl::compiled_object(&sql)) {
+      Some(object) => object,
+      None => return Err(err),
+    };
+    let owner = object.owner.as_ref().map(String::as_str);
+    let mut diagnostics = Vec::new();
+    for kind in &object.kinds {
+      match self.conn.compile_diagnostics(owner, kind, &object.name) {
+        Ok(d) => diagnostics.extend(d),
+        Err(_) => return Err(err),
+      }
+    }
+    // Сообщений может не оказаться, если объект определен неверно, тогда исходная ошибка информативнее
+    if diagnostics.is_empty() {
+      return Err(err);
+    }
+    // Объект, скомпилированный только с предупреждениями, пригоден для использования
+    if diagnostics.iter().all(|d| d.warning) {
+      *self.warnings.borrow_mut() = diagnostics;
+      return Ok(());
+    }
+    Err(Error::Compile { object: object.name, diagnostics: diagnostics })
+  }
+  /// Получает предупреждения компилятора PL/SQL для хранимого объекта, созданного или перекомпилированного при
+  /// последнем выполнении выражения методом [`execute()`][1] без ошибок компиляции. Если предупреждений не было или
+  /// выражение не компилирует хранимый объект, возвращает пустой список.
+  ///
+  /// Сервер сообщает о предупреждениях, только если они включены в сессии, например, выражением
+  /// `ALTER SESSION SET PLSQL_WARNINGS = 'ENABLE:ALL'`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу, сообщения получаются при выполнении выражения.
+  ///
+  /// [1]: #method.execute
+  pub fn compile_warnings(&self) -> Vec<Diagnostic> {
+    self.warnings.borrow().clone()
+  }
+  /// Закрывает выражение, удаляя его из кеша выражений соединения, так что следующая подготовка выражения с тем же
+  /// ключом выполнит повторный разбор. Для выражений, подготовленных без использования кеша, аналогично простому
+  /// закрытию выражения.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCIStmtRelease()`][1] в режиме `OCI_STRLS_CACHE_DELETE`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17170
+  #[inline]
+  pub fn evict(mut self) {
+    self.evict = true;
+  }
   /// Получает информацию о типе выражения.
   pub fn get_type(&self) -> Result<StatementType> {
     let ty: u16 = try!(self.get_(Attr::StmtType, self.error()));
@@ -352,21 +994,62 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   ///
   /// # OCI вызовы
   /// Для выполнения выражения непосредственно при вызове данной функции используется OCI-вызов [`OCIStmtExecute()`][2]. Для последующего
-  /// извлечения данных через итератор используется вызов [`OCIStmtFetch2()`][3], один на каждую итерацию (данное поведение будет улучшено
-  /// в дальнейшем, для получения результатов порциями некоторого настраиваемого размера).
+  /// извлечения данных через итератор используется вызов [`OCIStmtFetch2()`][3], один на каждую порцию из [`fetch_size()`][4] строк.
   ///
   /// # Запросы к серверу (1..)
-  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][2]. Каждая итерация выполняет по одному
-  /// вызову [`OCIStmtFetch2()`][3].
+  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][2]. Каждые [`fetch_size()`][4] итераций
+  /// выполняют по одному вызову [`OCIStmtFetch2()`][3].
   ///
   /// [1]: #method.execute
   /// [2]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
   /// [3]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  /// [4]: #method.fetch_size
   pub fn query(&mut self) -> Result<RowSet> {
-    try!(self.execute_impl(0, 0, Default::default()));
+    // Курсоры, полученные из базы, уже выполнены сервером
+    if !self.cursor {
+      try!(self.execute_impl(0, 0, ExecuteMode::default() as u32));
+    }
 
     RowSet::new(self)
   }
+  /// Выполняет `SELECT`-выражение и возвращает прокручиваемый набор результатов, по которому можно перемещаться в
+  /// произвольном направлении: к первой, последней, предыдущей строке или строке с заданным номером. Набор доступен
+  /// только для чтения.
+  ///
+  /// Прокручиваемые курсоры требуют больше ресурсов на сервере, поэтому для простого последовательного чтения
+  /// результатов стоит использовать метод [`query()`][1].
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("select level from dual connect by level <= 10").unwrap();
+  /// let rs = stmt.query_scrollable().unwrap();
+  ///
+  /// let last = rs.last().unwrap().unwrap();
+  /// assert_eq!(Some(10), last.get(0).unwrap());
+  /// let third = rs.absolute(3).unwrap().unwrap();
+  /// assert_eq!(Some(3), third.get(0).unwrap());
+  /// assert_eq!(3, rs.current_position().unwrap());
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Для выполнения выражения непосредственно при вызове данной функции используется OCI-вызов [`OCIStmtExecute()`][2]
+  /// в режиме `OCI_STMT_SCROLLABLE_READONLY`. Каждое перемещение по набору выполняет вызов [`OCIStmtFetch2()`][3].
+  ///
+  /// # Запросы к серверу (1)
+  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][2].
+  ///
+  /// [1]: #method.query
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  pub fn query_scrollable(&mut self) -> Result<ScrollableRowSet> {
+    try!(self.execute_impl(0, 0, ExecuteMode::StmtScrollableReadonly as u32));
+
+    ScrollableRowSet::new(self)
+  }
   /// Выполняет любой запрос. В случае выполнения `INSERT/UPDATE/DELETE` запроса возвращает количество строк,
   /// затронутых запросом (т.е. количество добавленных/обновленных/удаленных строк). Для DDL выражений (например,
   /// `create table`) возвращает `0`.
@@ -374,71 +1057,931 @@ impl<'conn, 'key> Statement<'conn, 'key> {
   /// Для получения результата от `SELECT` выражения после выполнения данной функции вызовите метод [`get_last_rowset`][1],
   /// либо вместо данного метода воспользуйтесь методом [`query()`][2].
   ///
+  /// Если выражение создало или перекомпилировало хранимый объект (процедуру, пакет, представление и т.п.), но тот
+  /// содержит ошибки компиляции, возвращает ошибку [`Error::Compile`][5] с сообщениями компилятора для объекта. Если
+  /// объект скомпилирован только с предупреждениями, выполнение считается успешным, а предупреждения можно получить
+  /// методом [`compile_warnings()`][6].
+  ///
   /// # OCI вызовы
   /// Для выполнения выражения непосредственно при вызове данной функции используется OCI-вызов [`OCIStmtExecute()`][3].
   /// Для последующего  получения количества затронутых строк используется вызов [`OCIAttrGet()`][4].
   ///
-  /// # Запросы к серверу (1)
-  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][3].
+  /// # Запросы к серверу (1..3)
+  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][3]. Если объект
+  /// скомпилирован с ошибками или предупреждениями, выполняется еще один запрос для получения сообщений компилятора
+  /// (два для перекомпиляции и спецификации, и тела пакета или типа).
   ///
   /// [1]: #method.get_last_rowset
   /// [2]: #method.query
   /// [3]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
   /// [4]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  /// [5]: ../error/enum.Error.html#variant.Compile
+  /// [6]: #method.compile_warnings
   pub fn execute(&self) -> Result<usize> {
     let count = match try!(self.get_type()) {
       StatementType::SELECT => 0,
       _ => 1,
     };
-    try!(self.execute_impl(count, 0, Default::default()));
+    self.warnings.borrow_mut().clear();
+    if let Err(e) = self.execute_impl(count, 0, ExecuteMode::default() as u32) {
+      try!(self.check_compile(e));
+    }
 
     Ok(try!(self.row_count()) as usize)
   }
-  /// Получает результат последнего исполненного выражения, если это было `SELECT`-выражение и `None` в противном случае.
-  pub fn get_last_rowset(&mut self) -> Result<Option<RowSet>> {
-    match try!(self.get_type()) {
-      StatementType::SELECT => RowSet::new(self).map(|r| Some(r)),
-      _ => Ok(None),
-    }
-  }
-
-  /// Ассоциирует с данным выражением адрес буфера, из которого извлекать данные для заданной переменной.
+  /// Выполняет `INSERT/UPDATE/DELETE` запрос указанное количество раз за один запрос к серверу, используя при `i`-ой
+  /// итерации `i`-ые элементы массивов, связанных с выражением методом [`bind_array()`][1]. Возвращает общее количество
+  /// строк, затронутых всеми итерациями.
   ///
-  /// # Параметры
-  /// - `index`:
-  ///   Порядковый номер (нумерация с 0) или символьное имя переменной в запросе.
-  /// - `param`:
-  ///   Связываемые данные. Должны дожить до вызова [`execute`][1] или [`query`][2].
+  /// Все переменные выражения должны быть связаны массивами, содержащими не менее `iters` элементов, иначе будет
+  /// возвращена ошибка [`ArrayTooShort`][2]. Если одна из итераций завершится ошибкой, выполнение прерывается, а
+  /// изменения, сделанные предыдущими итерациями, остаются в текущей транзакции.
+  /// При `iters`, равном `0`, выражение не выполняется и возвращается `0`.
   ///
-  /// # OCI вызовы
-  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][3] или [`OCIBindByPos()`][4], в зависимости от
-  /// того, какой тип параметра передан в `index`. Когда будет поддержано связывание функций для динамического
-  /// предоставления данных, для них будет осуществляться вызов [`OCIBindDynamic()`][5].
+  /// # Пример
+  /// ```no_run
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("insert into test_bind (id, name) values (:id, :name)").unwrap();
+  /// stmt.bind_array(":id", &[1i32, 2, 3]).unwrap();
+  /// stmt.bind_array(":name", &[Some("one"), None, Some("three")]).unwrap();
+  /// assert_eq!(3, stmt.execute_many(3).unwrap());
+  /// # conn.rollback().unwrap();
+  /// ```
   ///
-  /// # Запросы к серверу (0)
-  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  /// # OCI вызовы
+  /// Для выполнения выражения используется один OCI-вызов [`OCIStmtExecute()`][3] с параметром `iters`, равным
+  /// количеству итераций. Для последующего получения количества затронутых строк используется вызов [`OCIAttrGet()`][4].
   ///
-  /// # Unsafe
-  /// Функция небезопасная по той причине, что параметр `param` должен дожить до вызова [`execute`][1] или [`query`][2].
-  /// К сожалению, пока неясно, как заставить компилятор форсировать данное требование.
+  /// # Запросы к серверу (1)
+  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][3], независимо от
+  /// количества итераций.
   ///
-  /// [1]: #method.execute
-  /// [2]: #method.query
-  /// [3]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
-  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
-  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17142
-  pub unsafe fn bind<'i, 'p, I, P>(&'p mut self, index: I, param: P) -> Result<()>
-    where I: Into<BindIndex<'i>>,
-          P: Into<BindInfo<'p>> + 'p
-  {
-    let index = index.into();
-    let info = param.into();
+  /// [1]: #method.bind_array
+  /// [2]: ../error/enum.Error.html#variant.ArrayTooShort
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  pub fn execute_many(&self, iters: usize) -> Result<usize> {
+    // OCI не допускает выполнения DML выражения с нулевым количеством итераций
+    if iters == 0 {
+      return Ok(0);
+    }
+    try!(self.check_arrays(iters));
+    try!(self.execute_impl(iters as u32, 0, ExecuteMode::default() as u32));
 
-    try!(self.bind_value(index, info, BindMode::default()));
-    Ok(())
+    Ok(try!(self.row_count()) as usize)
   }
-  /// Ассоциирует с указанным местом связывания функцию, каждый вызов которой отдает значение (или его
-  /// часть) для переменной связывания.
+  /// Выполняет `INSERT/UPDATE/DELETE` запрос указанное количество раз за один запрос к серверу аналогично методу
+  /// [`execute_many()`][1], но не прерывает выполнение при ошибке в одной из итераций. Вместо этого ошибки всех
+  /// неудавшихся итераций собираются и возвращаются вместе с количествами строк, затронутых каждой итерацией.
+  ///
+  /// Итерации, выполнившиеся успешно, остаются в текущей транзакции независимо от наличия ошибок в других итерациях,
+  /// поэтому решение о фиксации или откате транзакции остается за вызывающим кодом.
+  ///
+  /// Как и в методе [`execute_many()`][1], все переменные выражения должны быть связаны массивами, содержащими не
+  /// менее `iters` элементов, иначе выражение не выполняется и возвращается ошибка [`ArrayTooShort`][too_short].
+  ///
+  /// # Пример
+  /// ```no_run
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("insert into test_bind (id) values (:id)").unwrap();
+  /// stmt.bind_array(":id", &[1i32, 1, 2]).unwrap();
+  /// let res = stmt.execute_batch(3).unwrap();
+  /// for e in &res.errors {
+  ///   println!("row {} failed: {}", e.offset, e.info.message);
+  /// }
+  /// println!("inserted {} rows", res.total());
+  /// ```
+  ///
+  /// Количества строк, затронутые отдельными итерациями, возвращаются, только если и клиент, и сервер имеют версию
+  /// не ниже 12.1. В противном случае список [`row_counts`][counts] пуст, но ошибки итераций и общее количество
+  /// затронутых строк возвращаются.
+  ///
+  /// # OCI вызовы
+  /// Для выполнения выражения используется один OCI-вызов [`OCIStmtExecute()`][2] в режиме `OCI_BATCH_ERRORS`, к
+  /// которому для версий 12.1 и выше добавляется `OCI_RETURN_ROW_COUNT_ARRAY`. Для получения количеств затронутых
+  /// строк и ошибок используются вызовы [`OCIAttrGet()`][3], для получения хендлов ошибок отдельных итераций --
+  /// [`OCIParamGet()`][4]. Для хранения
+  /// ошибки итерации создается и уничтожается хендл ошибки, соответственно функциями [`OCIHandleAlloc()`][new] и
+  /// [`OCIHandleFree()`][end].
+  ///
+  /// # Запросы к серверу (1..2)
+  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][2], независимо от
+  /// количества итераций. При первом пакетном выполнении в соединении выполняется еще один запрос для получения
+  /// версии сервера.
+  ///
+  /// [1]: #method.execute_many
+  /// [counts]: ./struct.BatchResult.html#structfield.row_counts
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17136
+  /// [new]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#GUID-C5BF55F7-A110-4CB5-9663-5056590F12B5
+  /// [end]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm
+  /// [too_short]: ../error/enum.Error.html#variant.ArrayTooShort
+  pub fn execute_batch(&self, iters: usize) -> Result<BatchResult> {
+    // OCI не допускает выполнения DML выражения с нулевым количеством итераций
+    if iters == 0 {
+      return Ok(BatchResult { row_counts: Vec::new(), errors: Vec::new(), total: 0 });
+    }
+    try!(self.check_arrays(iters));
+    // Количества строк по итерациям появились в 12.1, более старые клиент и сервер отвергают такой режим
+    let counts = try!(self.conn.supports(Version::minor(12, 1)));
+    let mut mode = ExecuteMode::BatchErrors as u32;
+    if counts {
+      mode |= ExecuteMode::ReturnRowCountArray as u32;
+    }
+    match self.execute_impl(iters as u32, 0, mode) {
+      // При наличии ошибок в отдельных итерациях возвращается OCI_SUCCESS_WITH_INFO (ORA-24381)
+      Ok(_) | Err(Error::Db(DbError::Info(_))) => {},
+      Err(e) => return Err(e),
+    }
+
+    Ok(BatchResult {
+      row_counts: if counts { try!(self.row_counts()) } else { Vec::new() },
+      errors: try!(self.batch_errors()),
+      total: try!(self.row_count()),
+    })
+  }
+  /// Получает количество строк, затронутых каждой итерацией последнего выполнения в режиме `OCI_RETURN_ROW_COUNT_ARRAY`.
+  fn row_counts(&self) -> DbResult<Vec<u64>> {
+    let mut ptr: *const u64 = ptr::null();
+    let mut len: u32 = 0;
+    try!(unsafe { self.get(&mut ptr as *mut *const u64 as *mut c_void, &mut len, Attr::DmlRowCountArray, self.error()) });
+    if ptr.is_null() {
+      return Ok(Vec::new());
+    }
+    // Массив принадлежит хендлу выражения, поэтому его нужно скопировать
+    Ok(unsafe { slice::from_raw_parts(ptr, len as usize) }.to_vec())
+  }
+  /// Получает ошибки отдельных итераций последнего выполнения в режиме `OCI_BATCH_ERRORS`.
+  fn batch_errors(&self) -> DbResult<Vec<BatchError>> {
+    let count: u32 = try!(self.get_(Attr::NumDmlErrors, self.error()));
+    let mut errors = Vec::with_capacity(count as usize);
+    if count == 0 {
+      return Ok(errors);
+    }
+    let row_error: Handle<OCIError> = try!(self.conn.new_handle());
+    for i in 0..count {
+      let mut native = row_error.native_mut() as *mut c_void;
+      let res = unsafe {
+        OCIParamGet(
+          self.error().native() as *const c_void, Handle::<OCIError>::holder_type(),
+          self.error().native_mut(),
+          &mut native, i
+        )
+      };
+      try!(self.error().check(res));
+      let row_error: Handle<OCIError> = Handle::borrowed(native as *mut OCIError);
+      let offset: u32 = try!(row_error.get_(Attr::DmlRowOffset, self.error()));
+
+      errors.push(BatchError { offset: offset as usize, info: row_error.info() });
+    }
+    Ok(errors)
+  }
+  /// Проверяет, связаны ли с выражением переменные, ссылающиеся на буферы, принадлежащие выражению или приложению.
+  fn has_buffers(&self) -> bool {
+    !(self.binds.is_empty() && self.arrays.is_empty() && self.ins.is_empty() &&
+      self.outs.is_empty() && self.returns.is_empty() && self.tables.is_empty())
+  }
+  /// Проверяет, что все связанные массивы содержат достаточное для указанного количества итераций количество элементов.
+  /// Переменные, связанные с буферами из одного элемента, считаются массивами из одного элемента.
+  fn check_arrays(&self, iters: usize) -> Result<()> {
+    let scalars = self.scalars.iter().map(|_| 1);
+    match self.arrays.iter().map(|&(_, ref a)| a.len()).chain(scalars).min() {
+      Some(len) if len < iters => Err(Error::ArrayTooShort { iters: iters, len: len }),
+      _ => Ok(()),
+    }
+  }
+  /// Возвращает итератор по неявным наборам результатов, которые PL/SQL блок вернул при последнем выполнении
+  /// выражения вызовами `DBMS_SQL.RETURN_RESULT`. Каждый набор представляется выполненным курсором, строки из которого
+  /// можно получить методом [`query()`][1]. Неявные наборы результатов поддерживаются, начиная с версии 12.1, причем
+  /// такую версию должны иметь и сервер, и клиентская библиотека: функции `OCIStmtGetNextResult()` в более старых
+  /// клиентах нет.
+  ///
+  /// Набор результатов следует извлечь до перехода к следующему элементу итератора. Хендлами наборов владеет выражение
+  /// и они освобождаются при его повторном выполнении, поэтому пока существует итератор или полученные из него курсоры,
+  /// выражение нельзя выполнить заново.
+  ///
+  /// # Пример
+  /// ```no_run
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("
+  ///   declare
+  ///     c1 sys_refcursor;
+  ///     c2 sys_refcursor;
+  ///   begin
+  ///     open c1 for select * from user_users;
+  ///     dbms_sql.return_result(c1);
+  ///     open c2 for select * from user_tables;
+  ///     dbms_sql.return_result(c2);
+  ///   end;").unwrap();
+  /// stmt.execute().unwrap();
+  ///
+  /// for result in stmt.implicit_results() {
+  ///   let mut result = result.unwrap();
+  ///   let rs = result.query().unwrap();
+  ///   println!("columns: {:?}", rs.columns());
+  ///   for row in &rs {
+  ///     let first: Option<String> = row.get(0).unwrap();
+  ///     println!("{:?}", first);
+  ///   }
+  /// }
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Каждый шаг итератора выполняет OCI-вызов [`OCIStmtGetNextResult()`][2].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.query
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm
+  #[inline]
+  pub fn implicit_results(&mut self) -> ImplicitResults {
+    ImplicitResults { stmt: self }
+  }
+  /// Получает результат последнего исполненного выражения, если это было `SELECT`-выражение и `None` в противном случае.
+  pub fn get_last_rowset(&mut self) -> Result<Option<RowSet>> {
+    match try!(self.get_type()) {
+      StatementType::SELECT => RowSet::new(self).map(|r| Some(r)),
+      _ => Ok(None),
+    }
+  }
+
+  /// Получает список переменных связывания (placeholder-ов) выражения в порядке их первого появления в тексте выражения.
+  /// Каждая переменная, встречающаяся в выражении несколько раз, возвращается один раз.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let stmt = conn.prepare("select :a, :b, :a from dual").unwrap();
+  /// let names: Vec<_> = stmt.bind_names().unwrap().into_iter().map(|b| (b.name, b.duplicated)).collect();
+  /// assert_eq!(vec![("A".to_owned(), true), ("B".to_owned(), false)], names);
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Выполняется один или несколько OCI-вызовов [`OCIStmtGetBindInfo()`][1], в зависимости от количества переменных.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm
+  pub fn bind_names(&self) -> Result<Vec<BindName>> {
+    // Количество переменных, получаемых за один вызов
+    const SIZE: usize = 32;
+
+    let mut result: Vec<BindName> = Vec::new();
+    let mut start = 0;
+    loop {
+      let mut names   = [ptr::null_mut(); SIZE];
+      let mut lens    = [0u8; SIZE];
+      let mut inds    = [ptr::null_mut(); SIZE];
+      let mut ind_lens= [0u8; SIZE];
+      let mut dups    = [0u8; SIZE];
+      let mut handles = [ptr::null_mut(); SIZE];
+      let mut found = 0;
+      let res = unsafe {
+        OCIStmtGetBindInfo(
+          self.native as *mut OCIStmt,
+          self.error().native_mut(),
+          SIZE as u32,
+          // В API оракла нумерация с 1, мы же придерживаемся традиционной с 0
+          start as u32 + 1,
+          &mut found,
+          names.as_mut_ptr(), lens.as_mut_ptr(),
+          inds.as_mut_ptr(), ind_lens.as_mut_ptr(),
+          dups.as_mut_ptr(),
+          handles.as_mut_ptr()
+        )
+      };
+      match self.error().check(res) {
+        Ok(_) => {},
+        // В выражении нет переменных
+        Err(DbError::NoData) => return Ok(result),
+        Err(e) => return Err(e.into()),
+      }
+      let total = found.abs() as usize;
+      let count = cmp::min(SIZE, total - start);
+      for i in 0..count {
+        let name = unsafe { slice::from_raw_parts(names[i] as *const u8, lens[i] as usize) };
+        let name = String::from_utf8_lossy(name).into_owned();
+        if dups[i] != 0 {
+          if let Some(b) = result.iter_mut().find(|b| b.name == name) {
+            b.duplicated = true;
+            continue;
+          }
+        }
+        result.push(BindName { name: name, pos: start + i, duplicated: false, bound: !handles[i].is_null() });
+      }
+      start += count;
+      if found >= 0 || start >= total {
+        return Ok(result);
+      }
+    }
+  }
+  /// Ассоциирует с данным выражением адрес буфера, из которого извлекать данные для заданной переменной.
+  ///
+  /// # Параметры
+  /// - `index`:
+  ///   Порядковый номер (нумерация с 0) или символьное имя переменной в запросе.
+  /// - `param`:
+  ///   Связываемые данные. Должны дожить до вызова [`execute`][1] или [`query`][2].
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][3] или [`OCIBindByPos()`][4], в зависимости от
+  /// того, какой тип параметра передан в `index`. Когда будет поддержано связывание функций для динамического
+  /// предоставления данных, для них будет осуществляться вызов [`OCIBindDynamic()`][5].
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// # Unsafe
+  /// Функция небезопасная по той причине, что параметр `param` должен дожить до вызова [`execute`][1] или [`query`][2].
+  /// К сожалению, пока неясно, как заставить компилятор форсировать данное требование.
+  ///
+  /// [1]: #method.execute
+  /// [2]: #method.query
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17142
+  pub unsafe fn bind<'i, 'p, I, P>(&'p mut self, index: I, param: P) -> Result<()>
+    where I: Into<BindIndex<'i>>,
+          P: Into<BindInfo<'p>> + 'p
+  {
+    let index = index.into();
+    let info = param.into();
+
+    try!(self.bind_in(index, info, false));
+    Ok(())
+  }
+  /// Связывает переданные параметры с выражением и выполняет его, аналогично методу [`execute()`][1]. Параметры
+  /// связаны с выражением только на время вызова, поэтому, в отличие от метода [`bind()`][2], данный метод безопасен:
+  /// значения копируются во внутренние буферы выражения, а после выполнения (в том числе неудачного) переменные,
+  /// значения которых были переданы, связываются с `NULL`-ом. Так как в PL/SQL блоках все переменные являются
+  /// `IN OUT`, сервер может изменить их значения, но изменения затрагивают только копии.
+  ///
+  /// Параметры могут быть переданы кортежем, срезом или массивом ссылок на значения (в этом случае они связываются по
+  /// позиции) или отображением из имени переменной в ссылку на значение. Подробнее см. типаж [`ToParams`][3].
+  ///
+  /// # Пример
+  /// ```no_run
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # use std::collections::HashMap;
+  /// # use oci::stmt::params::ToParam;
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("insert into test_params values (:id, :name)").unwrap();
+  /// let name = "one".to_owned();
+  /// // Значения разных типов -- кортеж
+  /// stmt.execute_with(&(&1, &name)).unwrap();
+  /// // Значения одного типа -- массив
+  /// stmt.execute_with(&[&"2", &"two"]).unwrap();
+  /// // Связывание по имени
+  /// let mut params: HashMap<&str, &ToParam> = HashMap::new();
+  /// params.insert(":id", &3);
+  /// params.insert(":name", &"three");
+  /// stmt.execute_with(&params).unwrap();
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Для каждого параметра дважды выполняется OCI-вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в
+  /// зависимости от способа передачи параметров: до и после выполнения выражения. Для выполнения используется
+  /// OCI-вызов [`OCIStmtExecute()`][6].
+  ///
+  /// # Запросы к серверу (1)
+  /// Выполняется один запрос к серверу для выполнения выражения.
+  ///
+  /// [1]: #method.execute
+  /// [2]: #method.bind
+  /// [3]: ./params/trait.ToParams.html
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  /// [6]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  pub fn execute_with<P>(&mut self, params: &P) -> Result<usize>
+    where P: ToParams + ?Sized
+  {
+    let params = params.to_params();
+    try!(self.bind_params(&params));
+    let res = self.execute();
+    try!(self.unbind_params(&params));
+    res
+  }
+  /// Связывает переданные параметры с выражением, выполняет его и возвращает ленивый итератор по результатам, аналогично
+  /// методу [`query()`][1]. Параметры связаны с выражением только на время вызова, как и в методе [`execute_with()`][2].
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("select :a + :b from dual").unwrap();
+  /// let rs = stmt.query_with(&(&2, &3)).unwrap();
+  /// let row = rs.next().unwrap().unwrap();
+  /// assert_eq!(Some(5), row.get(0).unwrap());
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Для каждого параметра дважды выполняется OCI-вызов [`OCIBindByName()`][3] или [`OCIBindByPos()`][4], в
+  /// зависимости от способа передачи параметров: до и после выполнения выражения. Для выполнения используется
+  /// OCI-вызов [`OCIStmtExecute()`][5], для последующего извлечения данных через итератор -- [`OCIStmtFetch2()`][6].
+  ///
+  /// # Запросы к серверу (1..)
+  /// Непосредственно в момент вызова данной функции выполняется один вызов [`OCIStmtExecute()`][5]. Каждые
+  /// [`fetch_size()`][7] итераций выполняют по одному вызову [`OCIStmtFetch2()`][6].
+  ///
+  /// [1]: #method.query
+  /// [2]: #method.execute_with
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17163
+  /// [6]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  /// [7]: #method.fetch_size
+  pub fn query_with<P>(&mut self, params: &P) -> Result<RowSet>
+    where P: ToParams + ?Sized
+  {
+    {
+      let params = params.to_params();
+      try!(self.bind_params(&params));
+      // Значения связанных переменных нужны только в момент выполнения, извлечение строк их не использует
+      let res = self.execute_impl(0, 0, ExecuteMode::default() as u32);
+      try!(self.unbind_params(&params));
+      try!(res);
+    }
+    RowSet::new(self)
+  }
+  /// Связывает с выражением копии переданных параметров. Если связать один из параметров не удалось, уже связанные
+  /// переменные связываются с `NULL`-ом.
+  fn bind_params(&mut self, params: &[(BindIndex, BindInfo)]) -> DbResult<()> {
+    for (i, &(index, info)) in params.iter().enumerate() {
+      if let Err(e) = self.bind_in(index, info, true) {
+        // Ошибка связывания важнее ошибки отвязывания
+        let _ = self.unbind_params(&params[..i]);
+        return Err(e);
+      }
+    }
+    Ok(())
+  }
+  /// Связывает с `NULL`-ом переменные, с которыми были связаны переданные параметры, освобождая память, занятую
+  /// копиями их значений. `NULL` передается значением нулевой длины без индикатора, поэтому после этого переменные
+  /// не ссылаются ни на какие буферы и выражение может быть возвращено в кеш.
+  fn unbind_params(&mut self, params: &[(BindIndex, BindInfo)]) -> DbResult<()> {
+    for &(index, info) in params {
+      try!(self.bind_value(index, BindInfo::null(info.ty), ptr::null_mut(), BindMode::default()));
+      let key = array_key(index);
+      self.ins.retain(|&(ref k, _)| *k != key);
+      self.scalars.retain(|k| *k != key);
+    }
+    Ok(())
+  }
+  /// Ассоциирует с данным выражением массив значений для заданной переменной, используемый при [массивном выполнении][1]
+  /// выражения. Значения копируются во внутренний буфер выражения, поэтому, в отличие от [`bind()`][2], срез не обязан
+  /// жить до момента выполнения. Для передачи `NULL`-ов используйте срез из `Option<T>`.
+  ///
+  /// Буфер выделяется под размер максимального элемента, умноженный на количество элементов, поэтому не стоит
+  /// связывать таким образом массивы, в которых размер элементов сильно различается. Размер одного элемента не может
+  /// превышать 65535 байт, в противном случае возвращается ошибка [`Overflow`][3].
+  ///
+  /// Повторное связывание той же переменной заменяет ранее связанный с ней массив.
+  ///
+  /// # Параметры
+  /// - `index`:
+  ///   Порядковый номер (нумерация с 0) или символьное имя переменной в запросе.
+  /// - `values`:
+  ///   Значения переменной для каждой итерации выполнения выражения.
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в зависимости от
+  /// того, какой тип параметра передан в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.execute_many
+  /// [2]: #method.bind
+  /// [3]: ../error/enum.Error.html#variant.Overflow
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_array<'i, I, T>(&mut self, index: I, values: &[T]) -> Result<()>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let index = index.into();
+    let array = try!(BindArray::new(values));
+    try!(self.bind_buffer(index, array.as_ptr(), array.elem_size(), array.ty, array.is_null_ptr(), array.sizes_ptr(), 0, ptr::null_mut()));
+    // Данные массива находятся в куче, поэтому перемещение самого объекта не меняет их адреса. Массив, ранее
+    // связанный с той же переменной, больше не используется сервером и заменяется новым
+    let key = array_key(index);
+    self.scalars.retain(|k| *k != key);
+    match self.arrays.iter().position(|&(ref k, _)| *k == key) {
+      Some(pos) => self.arrays[pos].1 = array,
+      None => self.arrays.push((key, array)),
+    }
+    Ok(())
+  }
+  /// Связывает с выражением выходную (`OUT`) переменную, значение в которую запишет сервер при выполнении выражения,
+  /// например, выходной параметр хранимой процедуры. Возвращает описатель, по которому значение можно получить
+  /// методом [`get_out()`][1] после [выполнения][2] выражения.
+  ///
+  /// Для числовых типов размер буфера равен размеру типа Rust, для остальных выделяется буфер размером в 32767 байт,
+  /// максимальный размер строки `VARCHAR2` в PL/SQL.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("begin :sum := 2 + 3; :name := 'five'; end;").unwrap();
+  /// let sum  = stmt.bind_out::<i64, _>(":sum").unwrap();
+  /// let name = stmt.bind_out::<String, _>(":name").unwrap();
+  /// stmt.execute().unwrap();
+  ///
+  /// assert_eq!(Some(5), stmt.get_out(&sum).unwrap());
+  /// assert_eq!(Some("five".to_owned()), stmt.get_out(&name).unwrap());
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][3] или [`OCIBindByPos()`][4], в зависимости от
+  /// того, какой тип параметра передан в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.get_out
+  /// [2]: #method.execute
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_out<'i, T, I>(&mut self, index: I) -> Result<OutParam<T>>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let index = try!(self.bind_out_value(index.into(), OutBind::new::<T>()));
+    Ok(OutParam { stmt: self.id, index: index, _phantom: PhantomData })
+  }
+  /// Связывает с выражением входную-выходную (`IN OUT`) переменную: сервер получит переданное значение и запишет в
+  /// переменную новое при выполнении выражения. Возвращает описатель, по которому новое значение можно получить
+  /// методом [`get_out()`][1] после [выполнения][2] выражения.
+  ///
+  /// Значение копируется во внутренний буфер, поэтому, в отличие от [`bind()`][3], не обязано жить до выполнения
+  /// выражения. Размер буфера определяется так же, как в методе [`bind_out()`][4], но не меньше размера значения.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("begin :x := :x * 2; end;").unwrap();
+  /// let x = stmt.bind_inout(":x", &21i32).unwrap();
+  /// stmt.execute().unwrap();
+  ///
+  /// assert_eq!(Some(42), stmt.get_out(&x).unwrap());
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][5] или [`OCIBindByPos()`][6], в зависимости от
+  /// того, какой тип параметра передан в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.get_out
+  /// [2]: #method.execute
+  /// [3]: #method.bind
+  /// [4]: #method.bind_out
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [6]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_inout<'i, T, I>(&mut self, index: I, value: &T) -> Result<OutParam<T>>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let out = try!(OutBind::with_value(value));
+    let index = try!(self.bind_out_value(index.into(), out));
+    Ok(OutParam { stmt: self.id, index: index, _phantom: PhantomData })
+  }
+  /// Получает значение выходной переменной, записанное сервером при последнем выполнении выражения, или `None`, если
+  /// сервер записал в нее `NULL`.
+  ///
+  /// Если описатель получен при связывании переменной с другим выражением, возвращает ошибку [`InvalidParam`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.InvalidParam
+  pub fn get_out<'s, T>(&'s self, param: &OutParam<T>) -> Result<Option<T>>
+    where T: FromDB<'s>
+  {
+    try!(self.check_param(param.stmt));
+    self.outs[param.index].to(self.conn)
+  }
+  /// Связывает с выражением переменную из предложения `RETURNING ... INTO` DML выражения. Возвращает описатель, по
+  /// которому значения, возвращенные сервером, можно получить методом [`get_returning()`][1] после выполнения
+  /// выражения.
+  ///
+  /// Т.к. `UPDATE` и `DELETE` выражения могут затронуть несколько строк, а при [массивном выполнении][2] каждая
+  /// итерация может вернуть свой набор значений, буферы для значений выделяются динамически по запросу сервера.
+  /// Размер каждого буфера определяется так же, как в методе [`bind_out()`][3].
+  ///
+  /// # Пример
+  /// ```no_run
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("update test_returning set val = val + 1 returning id into :id").unwrap();
+  /// let id = stmt.bind_returning::<i32, _>(":id").unwrap();
+  /// stmt.execute().unwrap();
+  ///
+  /// let ids: Vec<Option<i32>> = stmt.get_returning(&id, 0).unwrap();
+  /// println!("updated: {:?}", ids);
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в зависимости от
+  /// того, какой тип параметра передан в `index`, и вызов [`OCIBindDynamic()`][6] для регистрации функций, выделяющих
+  /// буферы под значения.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.get_returning
+  /// [2]: #method.execute_many
+  /// [3]: #method.bind_out
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  /// [6]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17142
+  pub fn bind_returning<'i, T, I>(&mut self, index: I) -> Result<ReturningParam<T>>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let handle = try!(self.bind_value(index.into(), BindInfo::dynamic(T::ty()), ptr::null_mut(), BindMode::DataAtExec));
+    // Контекст хранится в куче, поэтому перемещение самого объекта не меняет его адреса
+    let ctx = Box::new(OutBindContext::new(out_size::<T>(), self.error().native_mut()));
+    let ptr = &*ctx as *const OutBindContext as *mut c_void;
+    let res = unsafe {
+      OCIBindDynamic(
+        handle,
+        self.error().native_mut(),
+        ptr, Some(null_in_bind_adapter),
+        ptr, Some(out_bind_adapter)
+      )
+    };
+    try!(self.error().check(res));
+    self.returns.push(ctx);
+    Ok(ReturningParam { stmt: self.id, index: self.returns.len() - 1, _phantom: PhantomData })
+  }
+  /// Получает значения переменной из предложения `RETURNING ... INTO`, возвращенные сервером при выполнении указанной
+  /// итерации (нумерация с 0) последнего выполнения выражения: по одному значению на каждую затронутую итерацией строку.
+  /// Если итерация не затронула ни одной строки, возвращает пустой вектор. При выполнении методом [`execute()`][1]
+  /// выполняется единственная итерация с номером `0`.
+  ///
+  /// Если описатель получен при связывании переменной с другим выражением, возвращает ошибку [`InvalidParam`][2].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.execute
+  /// [2]: ../error/enum.Error.html#variant.InvalidParam
+  pub fn get_returning<'s, T>(&'s self, param: &ReturningParam<T>, iter: usize) -> Result<Vec<Option<T>>>
+    where T: FromDB<'s> + AsDB
+  {
+    try!(self.check_param(param.stmt));
+    let rows = self.returns[param.index].rows();
+    match rows.get(iter) {
+      Some(values) => values.iter().map(|v| match v.as_slice() {
+        Some(slice) => T::from_db(T::ty(), slice, self.conn).map(Some),
+        None => Ok(None),
+      }).collect(),
+      None => Ok(Vec::new()),
+    }
+  }
+  /// Связывает с выражением выходную переменную типа `SYS_REFCURSOR`, в которой сервер вернет открытый курсор.
+  /// Возвращает описатель, по которому курсор можно получить методом [`get_cursor()`][1] после [выполнения][2]
+  /// выражения.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("begin open :cur for select level from dual connect by level <= 3; end;").unwrap();
+  /// let cur = stmt.bind_cursor(":cur").unwrap();
+  /// stmt.execute().unwrap();
+  ///
+  /// let mut cursor = stmt.get_cursor(&cur).unwrap().unwrap();
+  /// let rs = cursor.query().unwrap();
+  /// for row in &rs {
+  ///   let level: Option<u32> = row.get(0).unwrap();
+  ///   println!("level: {:?}", level);
+  /// }
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIHandleAlloc()`][3] для выделения хендла выражения, в котором будет открыт курсор,
+  /// и вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в зависимости от того, какой тип параметра передан
+  /// в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.get_cursor
+  /// [2]: #method.execute
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17134
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_cursor<'i, I>(&mut self, index: I) -> Result<CursorParam>
+    where I: Into<BindIndex<'i>>
+  {
+    let handle = try!(self.conn.new_handle());
+    let index = try!(self.bind_out_value(index.into(), OutBind::cursor(handle)));
+    Ok(CursorParam { stmt: self.id, index: index })
+  }
+  /// Получает курсор, открытый сервером в выходной переменной типа `SYS_REFCURSOR` при последнем выполнении
+  /// выражения, или `None`, если сервер записал в нее `NULL`. Курсор уже выполнен, поэтому строки из него можно
+  /// сразу получать методом [`query()`][1].
+  ///
+  /// Если описатель получен при связывании переменной с другим выражением, возвращает ошибку [`InvalidParam`][2].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.query
+  /// [2]: ../error/enum.Error.html#variant.InvalidParam
+  pub fn get_cursor<'s>(&'s self, param: &CursorParam) -> Result<Option<Statement<'s, 'static>>> {
+    try!(self.check_param(param.stmt));
+    // Хендлом курсора владеет буфер выходной переменной, поэтому курсор не может пережить данное выражение
+    Ok(self.outs[param.index].cursor_handle().map(|h| Statement::from_cursor(self.conn, h)))
+  }
+  /// Проверяет, что описатель выходной переменной, помеченный указанным номером выражения, получен при связывании
+  /// переменной с данным выражением.
+  #[inline]
+  fn check_param(&self, stmt: usize) -> Result<()> {
+    if stmt == self.id { Ok(()) } else { Err(Error::InvalidParam) }
+  }
+  /// Связывает буфер выходной переменной с выражением и запоминает его в списке выходных переменных. Возвращает
+  /// индекс буфера в этом списке.
+  fn bind_out_value(&mut self, index: BindIndex, mut out: OutBind) -> Result<usize> {
+    let (ptr, size, ty) = (out.as_ptr(), out.capacity(), out.ty);
+    try!(self.bind_buffer(index, ptr, size, ty, out.is_null_mut(), out.sizes_mut(), 0, ptr::null_mut()));
+    self.add_scalar(&array_key(index));
+    // Данные буфера находятся в куче, поэтому перемещение самого объекта не меняет их адреса
+    self.outs.push(out);
+    Ok(self.outs.len() - 1)
+  }
+  /// Связывает с выражением входной (`IN`) PL/SQL массив (`TABLE OF ... INDEX BY ...`), например, параметр хранимой
+  /// процедуры типа `TABLE OF NUMBER INDEX BY PLS_INTEGER`. В отличие от [`bind_array()`][1], весь массив передается
+  /// серверу как одно значение при единственном выполнении выражения. Элементы в PL/SQL получат индексы, начиная с 1.
+  ///
+  /// Значения копируются во внутренний буфер, поэтому, в отличие от [`bind()`][2], не обязаны жить до выполнения
+  /// выражения. Размер одного элемента не может превышать 65535 байт, в противном случае возвращается ошибка
+  /// [`Overflow`][3].
+  ///
+  /// # Пример
+  /// ```no_run
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("begin my_package.process(:ids); end;").unwrap();
+  /// stmt.bind_table(":ids", &[1, 2, 3]).unwrap();
+  /// stmt.execute().unwrap();
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в зависимости от
+  /// того, какой тип параметра передан в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.bind_array
+  /// [2]: #method.bind
+  /// [3]: ../error/enum.Error.html#variant.Overflow
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_table<'i, I, T>(&mut self, index: I, values: &[T]) -> Result<()>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let table = try!(BindTable::with_values(values, 0, false));
+    try!(self.bind_table_value(index.into(), table));
+    Ok(())
+  }
+  /// Связывает с выражением выходной (`OUT`) PL/SQL массив (`TABLE OF ... INDEX BY ...`), в который сервер может
+  /// записать до `capacity` элементов при выполнении выражения. Возвращает описатель, по которому элементы можно
+  /// получить методом [`get_table()`][1] после [выполнения][2] выражения.
+  ///
+  /// Размер буфера под каждый элемент определяется так же, как в методе [`bind_out()`][3]. Если сервер попытается
+  /// записать больше `capacity` элементов, выполнение выражения завершится ошибкой.
+  ///
+  /// # Пример
+  /// ```
+  /// # use oci::Environment;
+  /// # use oci::params::{ConnectParams, Credentials};
+  /// # let env = Environment::default();
+  /// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+  /// let mut stmt = conn.prepare("
+  ///   declare
+  ///     type t_numbers is table of number index by pls_integer;
+  ///     v t_numbers;
+  ///   begin
+  ///     for i in 1..3 loop v(i) := i * 10; end loop;
+  ///     :v := v;
+  ///   end;").unwrap();
+  /// let v = stmt.bind_table_out::<i32, _>(":v", 10).unwrap();
+  /// stmt.execute().unwrap();
+  ///
+  /// assert_eq!(vec![Some(10), Some(20), Some(30)], stmt.get_table(&v).unwrap());
+  /// ```
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в зависимости от
+  /// того, какой тип параметра передан в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.get_table
+  /// [2]: #method.execute
+  /// [3]: #method.bind_out
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_table_out<'i, T, I>(&mut self, index: I, capacity: usize) -> Result<TableParam<T>>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let index = try!(self.bind_table_value(index.into(), BindTable::new::<T>(capacity)));
+    Ok(TableParam { stmt: self.id, index: index, _phantom: PhantomData })
+  }
+  /// Связывает с выражением входной-выходной (`IN OUT`) PL/SQL массив (`TABLE OF ... INDEX BY ...`): сервер получит
+  /// переданные значения и может записать в массив до `capacity` элементов (но не меньше, чем было передано) при
+  /// выполнении выражения. Возвращает описатель, по которому новые элементы можно получить методом [`get_table()`][1]
+  /// после [выполнения][2] выражения.
+  ///
+  /// Значения копируются во внутренний буфер, поэтому не обязаны жить до выполнения выражения. Размер буфера под
+  /// каждый элемент определяется так же, как в методе [`bind_out()`][3], но не меньше размера самого длинного значения.
+  ///
+  /// # OCI вызовы
+  /// При каждом вызове выполняется OCI-вызов [`OCIBindByName()`][4] или [`OCIBindByPos()`][5], в зависимости от
+  /// того, какой тип параметра передан в `index`.
+  ///
+  /// # Запросы к серверу (0)
+  /// Ни одна из вызываемых функций не выполняет запросов к серверу.
+  ///
+  /// [1]: #method.get_table
+  /// [2]: #method.execute
+  /// [3]: #method.bind_out
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [5]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  pub fn bind_table_inout<'i, T, I>(&mut self, index: I, values: &[T], capacity: usize) -> Result<TableParam<T>>
+    where I: Into<BindIndex<'i>>,
+          T: AsDB
+  {
+    let table = try!(BindTable::with_values(values, capacity, true));
+    let index = try!(self.bind_table_value(index.into(), table));
+    Ok(TableParam { stmt: self.id, index: index, _phantom: PhantomData })
+  }
+  /// Получает элементы выходного PL/SQL массива, записанные сервером при последнем выполнении выражения. Элементы,
+  /// в которые сервер записал `NULL`, представлены значением `None`.
+  ///
+  /// Если описатель получен при связывании массива с другим выражением, возвращает ошибку [`InvalidParam`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ../error/enum.Error.html#variant.InvalidParam
+  pub fn get_table<'s, T>(&'s self, param: &TableParam<T>) -> Result<Vec<Option<T>>>
+    where T: FromDB<'s>
+  {
+    try!(self.check_param(param.stmt));
+    self.tables[param.index].to(self.conn)
+  }
+  /// Связывает буфер PL/SQL массива с выражением и запоминает его в списке PL/SQL массивов. Возвращает индекс
+  /// буфера в этом списке.
+  fn bind_table_value(&mut self, index: BindIndex, mut table: BindTable) -> Result<usize> {
+    let (ptr, size, ty, max_len) = (table.as_ptr(), table.elem_size(), table.ty, table.capacity());
+    try!(self.bind_buffer(index, ptr, size, ty, table.is_null_mut(), table.sizes_mut(), max_len, table.len_mut()));
+    self.add_scalar(&array_key(index));
+    // Данные буфера находятся в куче, поэтому перемещение самого объекта не меняет их адреса
+    self.tables.push(table);
+    Ok(self.tables.len() - 1)
+  }
+  /// Ассоциирует с указанным местом связывания функцию, каждый вызов которой отдает значение (или его
+  /// часть) для переменной связывания.
   pub fn bind_fn<'i, I, F, T>(&mut self, index: I, mut func: F) -> Result<()>
     where I: Into<BindIndex<'i>>,
           F: FnMut(u32, u32) -> T + 'conn,
@@ -447,7 +1990,7 @@ impl<'conn, 'key> Statement<'conn, 'key> {
     let index = index.into();
     let info = BindInfo::dynamic(T::ty());
 
-    let handle = try!(self.bind_value(index, info, BindMode::DataAtExec));
+    let handle = try!(self.bind_value(index, info, ptr::null_mut(), BindMode::DataAtExec));
     try!(self.bind_dynamic(handle, move |_, v, iter, index, _| {
       let is_null = match func(iter, index).as_db() {
         Some(slice) => { v.extend_from_slice(slice); false },
@@ -457,12 +2000,113 @@ impl<'conn, 'key> Statement<'conn, 'key> {
     }));
     Ok(())
   }
+  /// Ассоциирует с указанным местом связывания читателя, из которого при выполнении выражения значение
+  /// будет передано серверу по частям, не загружая его в память целиком. Данные передаются как двоичные
+  /// (`LONG RAW`), что подходит для столбцов типов `BLOB` и `LONG RAW`. Для символьных столбцов используйте
+  /// метод [`bind_text_reader()`][1].
+  ///
+  /// Читатель потребляется при первом выполнении выражения. Если при чтении возникнет ошибка, выполнение
+  /// выражения будет прервано и вернет ее в виде ошибки [`Error::Io`][5].
+  ///
+  /// # OCI вызовы
+  /// Непосредственно при вызове данной функции выполняются OCI вызовы [`OCIBindByPos()`][2]/[`OCIBindByName()`][3]
+  /// и [`OCIBindDynamic()`][4].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу, данные передаются при выполнении выражения.
+  ///
+  /// [1]: #method.bind_text_reader
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17142
+  /// [5]: ../error/enum.Error.html#variant.Io
+  #[inline]
+  pub fn bind_reader<'i, I, R>(&mut self, index: I, reader: R) -> Result<()>
+    where I: Into<BindIndex<'i>>,
+          R: Read + 'conn
+  {
+    self.bind_stream(index.into(), Type::LBI, reader)
+  }
+  /// Ассоциирует с указанным местом связывания читателя, из которого при выполнении выражения значение
+  /// будет передано серверу по частям, не загружая его в память целиком. Данные передаются как символьные
+  /// (`LONG`) в кодировке клиента, что подходит для столбцов типов `CLOB` и `LONG`. Для двоичных данных
+  /// используйте метод [`bind_reader()`][1].
+  ///
+  /// Читатель потребляется при первом выполнении выражения. Если при чтении возникнет ошибка, выполнение
+  /// выражения будет прервано и вернет ее в виде ошибки [`Error::Io`][5].
+  ///
+  /// # OCI вызовы
+  /// Непосредственно при вызове данной функции выполняются OCI вызовы [`OCIBindByPos()`][2]/[`OCIBindByName()`][3]
+  /// и [`OCIBindDynamic()`][4].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу, данные передаются при выполнении выражения.
+  ///
+  /// [1]: #method.bind_reader
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17141
+  /// [3]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17140
+  /// [4]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17142
+  /// [5]: ../error/enum.Error.html#variant.Io
+  #[inline]
+  #[allow(deprecated)]// Кусочная передача данных возможна только для типов LONG, поэтому используем их несмотря на устаревание
+  pub fn bind_text_reader<'i, I, R>(&mut self, index: I, reader: R) -> Result<()>
+    where I: Into<BindIndex<'i>>,
+          R: Read + 'conn
+  {
+    self.bind_stream(index.into(), Type::LNG, reader)
+  }
+  /// Связывает переменную в режиме `DataAtExec` с функцией, читающей данные из читателя порциями по
+  /// `PIECE_SIZE` байт. Чтобы сообщить серверу, какая порция последняя, функция всегда читает на одну
+  /// порцию вперед.
+  fn bind_stream<R>(&mut self, index: BindIndex, ty: Type, mut reader: R) -> Result<()>
+    where R: Read + 'conn
+  {
+    let handle = try!(self.bind_value(index, BindInfo::dynamic(ty), ptr::null_mut(), BindMode::DataAtExec));
+    // Следующая порция, прочитанная заранее, и признак того, что очередной вызов начинает новое значение
+    let mut ahead = Vec::new();
+    let mut first = true;
+    let read_error = self.read_error.clone();
+    try!(self.bind_dynamic(handle, move |_, v, _, _, _| {
+      v.clear();
+      if first {
+        if let Err(e) = reader.by_ref().take(PIECE_SIZE as u64).read_to_end(v) {
+          *read_error.borrow_mut() = Some(e);
+          return (false, Piece::One, true);
+        }
+      } else {
+        mem::swap(v, &mut ahead);
+      }
+      ahead.clear();
+      if !v.is_empty() {
+        if let Err(e) = reader.by_ref().take(PIECE_SIZE as u64).read_to_end(&mut ahead) {
+          *read_error.borrow_mut() = Some(e);
+          return (false, Piece::One, true);
+        }
+      }
+      let piece = match (first, ahead.is_empty()) {
+        (true,  true ) => Piece::One,
+        (true,  false) => Piece::First,
+        (false, false) => Piece::Next,
+        (false, true ) => Piece::Last,
+      };
+      // После последней порции значения следующий вызов относится уже к следующей итерации
+      first = ahead.is_empty();
+      (false, piece, false)
+    }));
+    Ok(())
+  }
 }
 impl<'conn, 'key> Drop for Statement<'conn, 'key> {
   fn drop(&mut self) {
+    // Хендлом курсора владеет породивший его объект, он же его и освободит
+    if self.cursor {
+      return;
+    }
     let keyPtr = self.key.map_or(0 as *const u8, |x| x.as_ptr());
     let keyLen = self.key.map_or(0 as u32      , |x| x.len() as u32);
-    let res = unsafe { OCIStmtRelease(self.native as *mut OCIStmt, self.error().native_mut(), keyPtr, keyLen, 0) };
+    // Кешированное выражение сохраняет связанные переменные, а их буферы освобождаются вместе с объектом
+    let mode = if self.evict || self.has_buffers() { ReleaseMode::CacheDelete } else { ReleaseMode::Default };
+    let res = unsafe { OCIStmtRelease(self.native as *mut OCIStmt, self.error().native_mut(), keyPtr, keyLen, mode as u32) };
 
     // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
     let _ = self.error().check(res);//.expect("OCIStmtRelease");
@@ -478,10 +2122,15 @@ impl<'conn, 'key> AttrHolder<OCIStmt> for Statement<'conn, 'key> {
 }
 
 impl<'conn, 'key> super::StatementPrivate for Statement<'conn, 'key> {
-  fn new<'c, 'k>(conn: &'c Connection<'c>, sql: &str, key: Option<&'k str>, syntax: Syntax) -> DbResult<Statement<'c, 'k>> {
+  fn new<'c, 'k>(conn: &'c Connection<'c>, sql: &str, key: Option<&'k str>, syntax: Syntax, mode: CachingMode) -> DbResult<Statement<'c, 'k>> {
     let mut stmt = ptr::null_mut();
     let keyPtr = key.map_or(0 as *const u8, |x| x.as_ptr());
     let keyLen = key.map_or(0 as u32      , |x| x.len() as u32);
+    // Для DDL выражений, компилирующих хранимые объекты, запрашиваются и предупреждения компилятора, а не только ошибки
+    let mut mode = mode as u32;
+    if ddl::compiled_object(sql).is_some() {
+      mode |= CachingMode::GetPLSQLWarnings as u32;
+    }
     let res = unsafe {
       OCIStmtPrepare2(
         conn.context.native_mut(),
@@ -491,13 +2140,32 @@ impl<'conn, 'key> super::StatementPrivate for Statement<'conn, 'key> {
         sql.as_ptr(), sql.len() as u32,
         // Ключ кеширования, по которому достанется запрос, если он был закеширован
         keyPtr, keyLen,
-        syntax as u32, CachingMode::Default as u32
+        syntax as u32, mode
       )
     };
-    return match res {
-      0 => Ok(Statement { conn: conn, native: stmt, key: key, binds: Vec::new() }),
-      e => Err(conn.error().decode(e)),
+    let infos = match conn.error().check(res) {
+      Ok(_) => Vec::new(),
+      Err(DbError::Info(infos)) => infos,
+      Err(e) => return Err(e),
     };
+    let mut stmt = Statement { conn: conn, native: stmt, id: NEXT_ID.fetch_add(1, Ordering::Relaxed), key: key, binds: Vec::new(), read_error: Rc::new(RefCell::new(None)), arrays: Vec::new(), scalars: Vec::new(), ins: Vec::new(), outs: Vec::new(), returns: Vec::new(), tables: Vec::new(), fetch_size: 1, cursor: false, evict: false, warnings: RefCell::new(Vec::new()) };
+    // Если выражение с указанным ключом не найдено в кеше, оно подготавливается заново, о чем сообщается
+    // предупреждением ORA-24431. При закрытии выражение будет помещено в кеш с этим ключом.
+    if infos.iter().any(|i| i.code != 24431) {
+      // Хендл уже выделен, поэтому выражение освобождается, не попадая в кеш
+      stmt.evict = true;
+      return Err(DbError::Info(infos));
+    }
+
+    // Умолчания соединения для получения строк заранее
+    if let Some(rows) = conn.prefetch_rows.get() {
+      // Attr::Position имеет то же значение, что и OCI_ATTR_PREFETCH_ROWS, см. `set_prefetch_rows()`
+      try!(stmt.set_(rows, Attr::Position, conn.error()));
+    }
+    if let Some(bytes) = conn.prefetch_memory.get() {
+      try!(stmt.set_(bytes, Attr::PrefetchMemory, conn.error()));
+    }
+    Ok(stmt)
   }
 }
 trait RowSetPrivate<'stmt> : Sized {
@@ -0,0 +1,120 @@
+//! Содержит типажи, позволяющие безопасно связать с выражением набор параметров на время одного его выполнения.
+//! См. методы [`execute_with()`][1] и [`query_with()`][2].
+//!
+//! [1]: ../struct.Statement.html#method.execute_with
+//! [2]: ../struct.Statement.html#method.query_with
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use convert::{AsDB, BindInfo};
+use stmt::index::BindIndex;
+
+/// Значение, которое можно связать с переменной выражения. Реализован для всех типов, реализующих [`AsDB`][1].
+/// В отличие от него, может использоваться в виде типажа-объекта, что позволяет передавать в одном срезе или
+/// отображении значения разных типов.
+///
+/// [1]: ../../convert/trait.AsDB.html
+pub trait ToParam {
+  /// Получает информацию для связывания данного значения.
+  fn to_param(&self) -> BindInfo;
+}
+impl<T: AsDB + ?Sized> ToParam for T {
+  #[inline]
+  fn to_param(&self) -> BindInfo {
+    BindInfo::from(self)
+  }
+}
+
+/// Набор параметров, связываемых с выражением на время его выполнения.
+///
+/// Реализован для:
+/// - кортежей (до 12 элементов) из ссылок на значения -- значения связываются по позиции;
+/// - срезов и массивов (до 16 элементов) из ссылок на значения -- значения связываются по позиции.
+///   Чтобы передать значения разных типов, используйте срез из `&ToParam`;
+/// - отображений `HashMap` и `BTreeMap` из имени переменной в ссылку на значение -- значения связываются по
+///   имени, которое, как и в методе [`bind()`][1], должно включать начальное двоеточие.
+///
+/// [1]: ../struct.Statement.html#method.bind
+pub trait ToParams {
+  /// Получает список индексов переменных и информацию для связывания значений с ними.
+  fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)>;
+}
+
+impl<'v, T: ToParam + ?Sized> ToParams for [&'v T] {
+  fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)> {
+    self.iter().enumerate().map(|(i, v)| (BindIndex::Index(i), v.to_param())).collect()
+  }
+}
+impl<'v, T: ToParam + ?Sized> ToParams for Vec<&'v T> {
+  #[inline]
+  fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)> {
+    self[..].to_params()
+  }
+}
+/// Реализует типаж для массивов указанного размера, т.к. в Rust нет возможности реализовать
+/// его сразу для массивов всех размеров.
+macro_rules! array {
+  ($size:tt) => (
+    impl<'v, T: ToParam + ?Sized> ToParams for [&'v T; $size] {
+      #[inline]
+      fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)> {
+        self[..].to_params()
+      }
+    }
+  );
+}
+array!(1);
+array!(2);
+array!(3);
+array!(4);
+array!(5);
+array!(6);
+array!(7);
+array!(8);
+array!(9);
+array!(10);
+array!(11);
+array!(12);
+array!(13);
+array!(14);
+array!(15);
+array!(16);
+
+macro_rules! tuple {
+  ($($name:ident : $idx:tt),+) => (
+    impl<'v, $($name: ToParam + ?Sized),+> ToParams for ($(&'v $name,)+) {
+      fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)> {
+        vec![$((BindIndex::Index($idx), self.$idx.to_param())),+]
+      }
+    }
+  );
+}
+tuple!(A:0);
+tuple!(A:0, B:1);
+tuple!(A:0, B:1, C:2);
+tuple!(A:0, B:1, C:2, D:3);
+tuple!(A:0, B:1, C:2, D:3, E:4);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7, I:8);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7, I:8, J:9);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7, I:8, J:9, K:10);
+tuple!(A:0, B:1, C:2, D:3, E:4, F:5, G:6, H:7, I:8, J:9, K:10, L:11);
+
+impl<'v, K, V> ToParams for HashMap<K, &'v V>
+  where K: AsRef<str> + Eq + Hash,
+        V: ToParam + ?Sized
+{
+  fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)> {
+    self.iter().map(|(k, v)| (BindIndex::Name(k.as_ref()), v.to_param())).collect()
+  }
+}
+impl<'v, K, V> ToParams for BTreeMap<K, &'v V>
+  where K: AsRef<str> + Ord,
+        V: ToParam + ?Sized
+{
+  fn to_params<'a>(&'a self) -> Vec<(BindIndex<'a>, BindInfo<'a>)> {
+    self.iter().map(|(k, v)| (BindIndex::Name(k.as_ref()), v.to_param())).collect()
+  }
+}
@@ -1,20 +1,27 @@
 //! Содержит типы, используемые при извлечении данных из базы с помощью `SELECT`-ов.
 
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
 use Result;
 use convert::FromDB;
 use error::{self, Error};
 use error::DbError::{Info, NoData, Fault};
 use stmt::{Column, Statement};
 use stmt::index::RowIndex;
-use stmt::storage::DefineInfo;
+use stmt::storage::{DefineInfo, is_piecewise};
+use types::Type;
+
+use ffi::types::{DefineMode, FetchMode};
 
 /// Результат `SELECT`-выражения, представляющий одну строчку с данными из всей выборки.
 ///
-/// Является владельцем всей памяти, выделенной под хранение объектов, получаемых из базы
-/// данных. Существуют 2 типа объектов -- те, которые после выполнения запросов полностью
-/// материализуются на клиенте и хранятся в данном классе, и те, которые материализуются
-/// не полностью. В этом случае в данном классе хранится лишь локатор, по которому можно
-/// получить данные из соединения в дальнейшем.
+/// Разделяет владение памятью, выделенной под хранение объектов, получаемых из базы данных, с
+/// другими строками, извлеченными за тот же запрос к серверу. Память освобождается, когда все
+/// строки порции будут уничтожены. Существуют 2 типа объектов -- те, которые после выполнения
+/// запросов полностью материализуются на клиенте и хранятся в данном классе, и те, которые
+/// материализуются не полностью. В этом случае в данном классе хранится лишь локатор, по
+/// которому можно получить данные из соединения в дальнейшем.
 ///
 /// Ко второму типу относятся все столбцы с временными метками (`TIMESTAMP ...`, но не
 /// `DATE`) и столбцы с LOB-данными (`CLOB`, `NCLOB`, `BLOB`, `BFILE`).
@@ -22,25 +29,17 @@ use stmt::storage::DefineInfo;
 pub struct Row<'rs> {
   /// Выборка, из которой получен данный объект.
   rs: &'rs RowSet<'rs>,
-  /// Массив данных для каждой колонки.
-  data: Vec<DefineInfo<'rs>>,
+  /// Массив данных для каждой колонки всех строк порции, в которой была извлечена данная строка.
+  data: Rc<Vec<DefineInfo<'rs>>>,
+  /// Номер данной строки в порции.
+  index: usize,
   /// Диагностическая информация, полученная при извлечении данных, если есть.
   /// Например, может содержать информацию о том, что значение колонки было получено не полностью
-  /// из-за недостаточного размера принимающего буфера.
+  /// из-за недостаточного размера принимающего буфера. Информация относится ко всей порции строк,
+  /// извлеченных за один запрос к серверу.
   pub info: Option<Vec<error::Info>>,
 }
 impl<'rs> Row<'rs> {
-  fn new(rs: &'rs RowSet) -> Result<Self> {
-    let mut data: Vec<DefineInfo> = Vec::with_capacity(rs.columns.len());
-
-    for c in &rs.columns {
-      data.push(try!(DefineInfo::new(rs.stmt, c)));
-      // unwrap делать безопасно, т.к. мы только что вставили в массив данные
-      try!(rs.stmt.define(c.pos as u32, c.type_, data.last_mut().unwrap(), Default::default()));
-    }
-
-    Ok(Row { rs: rs, data: data, info: None })
-  }
   /// Получает описание столбца списка выбора результата `SELECT`-выражения по указанному индексу.
   #[inline]
   pub fn column<I: RowIndex>(&self, index: I) -> Result<&Column> {
@@ -68,22 +67,63 @@ impl<'rs> Row<'rs> {
   ///    таким образом, невозможно отдать ссылку на него, не сохранив предварительно внутри структуры `Row`
   pub fn get<T: FromDB<'rs>, I: RowIndex>(&self, index: I) -> Result<Option<T>> {
     let col = try!(self.column(index));
-    self.data[col.pos].to(col.type_, self.rs.stmt.connection())
+    self.data[col.pos].to(self.index, col.type_, self.rs.stmt.connection())
+  }
+  /// Получает курсор, извлеченный из столбца `CURSOR(...)` по заданному индексу, в виде выражения, из которого можно
+  /// получить строки методом [`query()`][1], или `None`, если в столбце находится `NULL`.
+  ///
+  /// Хендлом курсора владеет порция строк, в которой была извлечена данная строка, поэтому курсор можно использовать
+  /// только пока существует эта строка.
+  ///
+  /// Возвращает ошибку, если индекс некорректен или столбец не является курсором.
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: ../struct.Statement.html#method.query
+  pub fn get_cursor<'r, I: RowIndex>(&'r self, index: I) -> Result<Option<Statement<'r, 'static>>> {
+    let col = try!(self.column(index));
+    match col.type_ {
+      Type::CUR | Type::RSET => {},
+      t => return Err(Error::Conversion(t)),
+    }
+    Ok(self.data[col.pos].cursor(self.index).map(|h| Statement::from_cursor(self.rs.stmt.connection(), h)))
   }
 }
 
+/// Порция строк, извлеченная из базы за один запрос к серверу.
+#[derive(Debug)]
+struct Batch<'stmt> {
+  /// Буферы с данными для каждой колонки, связанные с выражением. Разделяются со строками порции.
+  data: Rc<Vec<DefineInfo<'stmt>>>,
+  /// Количество строк, под которое выделены буферы.
+  size: u32,
+  /// Количество строк, реально извлеченных в буферы.
+  count: usize,
+  /// Диагностическая информация, полученная при извлечении порции.
+  info: Option<Vec<error::Info>>,
+}
+
 /// Ленивый набор результатов, полученный при выполнении `SELECT` выражения. Реально данные извлекаются при итерации по набору,
 /// именно поэтому метод [`query()`][1], возвращающий их, является `mut` методом.
 ///
-/// В настоящий момент при итерации по набору получается одна строка за раз, с выполнением обращения к серверу.
+/// Строки извлекаются с сервера порциями, размер которых задается методом [`Statement::set_fetch_size()`][2], и затем
+/// выдаются по одной из буфера.
 ///
 /// [1]: ../struct.Statement.html#method.query
+/// [2]: ../struct.Statement.html#method.set_fetch_size
 #[derive(Debug)]
 pub struct RowSet<'stmt> {
   /// Выражение, выполнение которого дало данный набор результатов
   stmt: &'stmt Statement<'stmt, 'stmt>,
   /// Список колонок, которые извлекали из базы данных
   columns: Vec<Column>,
+  /// Последняя извлеченная из базы порция строк.
+  batch: RefCell<Option<Batch<'stmt>>>,
+  /// Номер следующей выдаваемой строки в текущей порции.
+  pos: Cell<usize>,
+  /// Признак того, что сервер уже вернул последнюю порцию строк.
+  finished: Cell<bool>,
 }
 impl<'stmt> RowSet<'stmt> {
   /// Получает выражение, которое породило данный набор результатов.
@@ -101,35 +141,108 @@ impl<'stmt> RowSet<'stmt> {
   /// выполняется паника текущего потока.
   ///
   /// # OCI вызовы
-  /// В настоящий момент при каждом вызове выполняется OCI-вызов [`OCIStmtFetch2()`][1], но это будет изменено в дальнейшем для выполнения
-  /// пакетных чтений сразу же по несколько элементов (размер пакета будет конфигурируемым).
+  /// Когда строки текущей порции заканчиваются, выполняется OCI-вызов [`OCIStmtFetch2()`][1], извлекающий следующую порцию
+  /// строк. Перед извлечением первой порции, а также если приложение все еще хранит строки предыдущей порции, выполняются
+  /// OCI-вызовы [`OCIDefineByPos()`][2] для каждой колонки, связывающие с выражением новые буферы.
   ///
-  /// # Запросы к серверу (1)
-  /// Каждый вызов данной функции приводит к одному запросу к серверу.
+  /// # Запросы к серверу (0..1)
+  /// Запрос к серверу выполняется только при извлечении новой порции строк, т.е. один раз на [`fetch_size()`][3] вызовов.
   ///
   /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  /// [2]: https://docs.oracle.com/database/122/LNOCI/bind-define-describe-functions.htm#LNOCI17143
+  /// [3]: ../struct.Statement.html#method.fetch_size
   pub fn next(&'stmt self) -> Result<Option<Row<'stmt>>> {
-    // Подготавливаем место в памяти для извлечения данных.
-    // TODO: его можно переиспользовать, незачем создавать каждый раз.
-    let mut r = Row::new(self).expect("Row::new failed");
-    match self.stmt.fetch(1, Default::default(), 0) {
-      Ok(_) => Ok(Some(r)),
-      Err(Info(data)) => {
-        r.info = Some(data);
-        Ok(Some(r))
+    let need_fetch = match *self.batch.borrow() {
+      Some(ref b) => self.pos.get() >= b.count,
+      None => true,
+    };
+    if need_fetch {
+      if self.finished.get() {
+        return Ok(None);
+      }
+      if !try!(self.fetch_rows(self.stmt.fetch_size(), FetchMode::Next, 0)) {
+        return Ok(None);
       }
-      Err(NoData) => Ok(None),
+      self.pos.set(0);
+    }
+    let pos = self.pos.get();
+    self.pos.set(pos + 1);
+
+    Ok(Some(self.row(pos)))
+  }
+  /// Создает строку с указанным номером из текущей порции.
+  fn row(&'stmt self, index: usize) -> Row<'stmt> {
+    let batch = self.batch.borrow();
+    // unwrap делать безопасно, т.к. строки выдаются только после извлечения порции
+    let batch = batch.as_ref().unwrap();
+    Row { rs: self, data: batch.data.clone(), index: index, info: batch.info.clone() }
+  }
+  /// Извлекает из базы в указанном режиме порцию из `size` строк, которая становится текущей. Возвращает `false`,
+  /// если строк не было извлечено.
+  ///
+  /// Буферы под значения связываются с выражением один раз и повторно используются для всех последующих порций.
+  /// Новые буферы выделяются и связываются только в том случае, если приложение еще хранит строки предыдущей порции,
+  /// т.к. они разделяют буферы с ней и не должны увидеть изменений.
+  fn fetch_rows(&'stmt self, size: u32, mode: FetchMode, index: i32) -> Result<bool> {
+    // Значения `LONG` и `LONG RAW` извлекаются по частям, что возможно только для одной строки за раз
+    let size = if self.columns.iter().any(|c| is_piecewise(c.type_)) { 1 } else { size };
+    // Если строк предыдущей порции больше нет, ее буферы принадлежат только набору и их можно переиспользовать
+    let reused = match self.batch.borrow_mut().take() {
+      Some(Batch { data, size: s, .. }) if s == size => Rc::try_unwrap(data).ok(),
+      _ => None,
+    };
+    let mut data = match reused {
+      Some(mut data) => {
+        for buf in &mut data {
+          buf.reset();
+        }
+        data
+      },
+      None => try!(self.define_buffers(size)),
+    };
+
+    // Второй элемент -- признак того, что сервер выполнил извлечение и количество извлеченных строк актуально
+    let (info, fetched) = match self.stmt.fetch_pieces(size, mode, index, &mut data) {
+      Ok(_) => (None, true),
+      Err(Error::Db(Info(data))) => (Some(data), true),
+      // Строк оказалось меньше, чем размер порции, но часть из них могла быть извлечена
+      Err(Error::Db(NoData)) => { self.finished.set(true); (None, true) },
       // ORA-01002: fetch out of sequence - если перезапустить итератор, из которого вычитаны все данные, вернется данная ошибка
-      Err(Fault(error::Info { code: 1002, .. })) => Ok(None),
-      Err(e) => Err(e.into()),
+      Err(Error::Db(Fault(error::Info { code: 1002, .. }))) => { self.finished.set(true); (None, false) },
+      Err(e) => return Err(e),
+    };
+    let count = if fetched { try!(self.stmt.rows_fetched()) as usize } else { 0 };
+    if count == 0 {
+      self.finished.set(true);
+    }
+    // Буферы сохраняются даже для пустой порции, чтобы использовать их при следующем извлечении
+    *self.batch.borrow_mut() = Some(Batch { data: Rc::new(data), size: size, count: count, info: info });
+    Ok(count > 0)
+  }
+  /// Выделяет буферы под `size` строк для каждого столбца и связывает их с выражением.
+  fn define_buffers(&self, size: u32) -> Result<Vec<DefineInfo<'stmt>>> {
+    let mut data: Vec<DefineInfo<'stmt>> = Vec::with_capacity(self.columns.len());
+    for c in &self.columns {
+      data.push(try!(DefineInfo::new(self.stmt, c, size as usize)));
+      // unwrap делать безопасно, т.к. мы только что вставили в массив данные
+      let buf = data.last_mut().unwrap();
+      let mode = if buf.is_piecewise() { DefineMode::DynamicFetch } else { Default::default() };
+      try!(self.stmt.define(c.pos as u32, c.type_, buf, mode));
     }
+    Ok(data)
   }
 }
 impl<'stmt> super::RowSetPrivate<'stmt> for RowSet<'stmt> {
   /// Создает набор из выражения. Запоминает описание столбцов выражения
   #[inline]
   fn new(stmt: &'stmt Statement) -> Result<Self> {
-    Ok(RowSet { stmt: stmt, columns: try!(stmt.columns()) })
+    Ok(RowSet {
+      stmt: stmt,
+      columns: try!(stmt.columns()),
+      batch: RefCell::new(None),
+      pos: Cell::new(0),
+      finished: Cell::new(false),
+    })
   }
 }
 impl<'stmt> Iterator for &'stmt RowSet<'stmt> {
@@ -138,4 +251,134 @@ impl<'stmt> Iterator for &'stmt RowSet<'stmt> {
   fn next(&mut self) -> Option<Self::Item> {
     RowSet::next(self).expect("`fetch` failed")
   }
-}
\ No newline at end of file
+}
+
+/// Прокручиваемый набор результатов, полученный при выполнении `SELECT` выражения методом [`query_scrollable()`][1].
+/// В отличие от [`RowSet`][2], позволяет перемещаться по результатам в произвольном направлении. Каждое перемещение
+/// извлекает из базы ровно одну строку.
+///
+/// [1]: ../struct.Statement.html#method.query_scrollable
+/// [2]: struct.RowSet.html
+#[derive(Debug)]
+pub struct ScrollableRowSet<'stmt> {
+  /// Обычный набор, используемый для хранения описания столбцов и извлечения строк.
+  rs: RowSet<'stmt>,
+}
+impl<'stmt> ScrollableRowSet<'stmt> {
+  /// Получает выражение, которое породило данный набор результатов.
+  #[inline]
+  pub fn statement(&self) -> &Statement<'stmt, 'stmt> {
+    self.rs.statement()
+  }
+  /// Получает список столбцов, которые содержатся в данном результате `SELECT`-а.
+  #[inline]
+  pub fn columns(&self) -> &[Column] {
+    self.rs.columns()
+  }
+  /// Перемещает курсор на первую строку набора и возвращает ее, или `None`, если набор пуст.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtFetch2()`][1] в режиме `OCI_FETCH_FIRST`.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  #[inline]
+  pub fn first(&'stmt self) -> Result<Option<Row<'stmt>>> {
+    self.fetch(FetchMode::First, 0)
+  }
+  /// Перемещает курсор на последнюю строку набора и возвращает ее, или `None`, если набор пуст.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtFetch2()`][1] в режиме `OCI_FETCH_LAST`.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  #[inline]
+  pub fn last(&'stmt self) -> Result<Option<Row<'stmt>>> {
+    self.fetch(FetchMode::Last, 0)
+  }
+  /// Перемещает курсор на следующую строку набора и возвращает ее, или `None`, если курсор находится на последней строке.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtFetch2()`][1] в режиме `OCI_FETCH_NEXT`.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  #[inline]
+  pub fn next(&'stmt self) -> Result<Option<Row<'stmt>>> {
+    self.fetch(FetchMode::Next, 0)
+  }
+  /// Перемещает курсор на предыдущую строку набора и возвращает ее, или `None`, если курсор находится на первой строке.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtFetch2()`][1] в режиме `OCI_FETCH_PRIOR`.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  #[inline]
+  pub fn prior(&'stmt self) -> Result<Option<Row<'stmt>>> {
+    self.fetch(FetchMode::Prior, 0)
+  }
+  /// Перемещает курсор на строку с указанным номером (нумерация с 1) и возвращает ее, или `None`, если строки с таким
+  /// номером в наборе нет. Отрицательные номера отсчитываются от конца набора, так, `-1` означает последнюю строку.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtFetch2()`][1] в режиме `OCI_FETCH_ABSOLUTE`.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  #[inline]
+  pub fn absolute(&'stmt self, n: i32) -> Result<Option<Row<'stmt>>> {
+    self.fetch(FetchMode::Absolute, n)
+  }
+  /// Перемещает курсор на `n` строк относительно текущей позиции (назад, если `n` отрицательно) и возвращает
+  /// строку в новой позиции, или `None`, если такой строки в наборе нет.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIStmtFetch2()`][1] в режиме `OCI_FETCH_RELATIVE`.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/statement-functions.htm#LNOCI17165
+  #[inline]
+  pub fn relative(&'stmt self, n: i32) -> Result<Option<Row<'stmt>>> {
+    self.fetch(FetchMode::Relative, n)
+  }
+  /// Получает номер строки (нумерация с 1), на которой находится курсор. До первого перемещения курсора равен `0`.
+  ///
+  /// # OCI вызовы
+  /// Выполняется OCI-вызов [`OCIAttrGet()`][1].
+  ///
+  /// # Запросы к серверу (0)
+  /// Функция не выполняет запросов к серверу.
+  ///
+  /// [1]: https://docs.oracle.com/database/122/LNOCI/handle-and-descriptor-functions.htm#LNOCI17130
+  #[inline]
+  pub fn current_position(&self) -> Result<u32> {
+    self.rs.stmt.current_position().map_err(Into::into)
+  }
+  /// Извлекает одну строку в указанном режиме.
+  fn fetch(&'stmt self, mode: FetchMode, index: i32) -> Result<Option<Row<'stmt>>> {
+    if !try!(self.rs.fetch_rows(1, mode, index)) {
+      return Ok(None);
+    }
+    Ok(Some(self.rs.row(0)))
+  }
+}
+impl<'stmt> super::RowSetPrivate<'stmt> for ScrollableRowSet<'stmt> {
+  #[inline]
+  fn new(stmt: &'stmt Statement) -> Result<Self> {
+    Ok(ScrollableRowSet { rs: try!(RowSet::new(stmt)) })
+  }
+}
@@ -1,120 +1,205 @@
 
+use std::cmp;
 use std::convert::{From, Into};
+use std::i32;
+use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_void;
 use std::ptr;
 use std::slice;
+use std::u16;
 
 use {Connection, Statement, Result};
-use convert::FromDB;
+use convert::{AsDB, BindInfo, FromDB};
+use error::Error;
 use stmt::Column;
 use types::Type;
 
-use ffi::{Descriptor, GenericDescriptor};// Основные типобезопасные примитивы
+use ffi::{Descriptor, GenericDescriptor, Handle};// Основные типобезопасные примитивы
 use ffi::DescriptorType;// Типажи для безопасного моста к FFI
 
+use ffi::native::{OCIDefine, OCIStmt};// FFI типы
 use ffi::native::time::{Timestamp, TimestampWithTZ, TimestampWithLTZ, IntervalYM, IntervalDS};
 use ffi::native::lob::{Lob, File};
+use ffi::types::OCIInd;
 
 /// TODO: Как сказано в http://docs.oracle.com/database/122/LNOCI/binding-and-defining-in-oci.htm#LNOCI16384,
 /// выходные буферы для операций define должны быть выровнены по 2 байта.
+///
+/// Хранит значения одной колонки для нескольких строк сразу, что позволяет извлекать строки из базы порциями.
 #[derive(Debug)]
 pub enum Storage<'d> {
   Vec {
-    /// Указатель на начало памяти, где будут храниться данные
-    ptr: *mut u8,
-    /// Количество байт, выделенной по указателю `ptr`.
-    capacity: usize,
-    /// Количество байт, реально используемое для хранения данных.
-    size: u16,
+    /// Память, где будут храниться данные всех строк, по `elem_size` байт на строку
+    data: Vec<u8>,
+    /// Количество байт, выделенное под значение одной строки.
+    elem_size: usize,
+    /// Количество байт, реально используемое для хранения данных каждой строки.
+    sizes: Vec<u16>,
+  },
+  Descriptor {
+    /// Дескрипторы для каждой строки. Хранятся для того, чтобы освободить их при разрушении хранилища.
+    #[allow(dead_code)]
+    descriptors: Vec<GenericDescriptor>,
+    /// Непрерывный массив указателей на данные дескрипторов, который передается в OCI.
+    ptrs: Vec<*const c_void>,
+    /// Время жизни окружения, из которого выделены дескрипторы. Не позволяет хранилищу пережить окружение.
+    phantom: PhantomData<&'d c_void>,
+  },
+  /// Значение столбца типа `LONG` или `LONG RAW` единственной строки, извлекаемое с сервера по частям.
+  Pieces {
+    /// Данные всех уже полученных частей значения.
+    data: Vec<u8>,
+    /// Количество байт в `data`, реально заполненных сервером.
+    filled: usize,
+    /// Размер последней запрошенной части. После получения части сервер записывает сюда количество байт, реально
+    /// помещенных в нее.
+    alen: u32,
+  },
+  Handle {
+    /// Хендлы выражений для каждой строки, в которые извлекаются курсоры. Хранятся для того, чтобы освободить их
+    /// при разрушении хранилища.
+    #[allow(dead_code)]
+    handles: Vec<Handle<OCIStmt>>,
+    /// Непрерывный массив указателей на хендлы, который передается в OCI.
+    ptrs: Vec<*const c_void>,
   },
-  Descriptor(GenericDescriptor<'d>),
 }
 impl<'d> Storage<'d> {
   /// Получает адрес блока памяти, который можно использовать для записи в него значений
   fn as_ptr(&mut self) -> *mut c_void {
     match *self {
-      Storage::Vec { ptr, .. } => ptr as *mut c_void,
-      Storage::Descriptor(ref mut d) => d.address_mut(),
+      Storage::Vec { ref mut data, .. } => data.as_mut_ptr() as *mut c_void,
+      Storage::Descriptor { ref mut ptrs, .. } |
+      Storage::Handle { ref mut ptrs, .. } => ptrs.as_mut_ptr() as *mut c_void,
+      // Буферы для частей значения предоставляются уже в процессе извлечения
+      Storage::Pieces { .. } => ptr::null_mut(),
     }
   }
-  /// Получает вместимость буфера
+  /// Получает вместимость буфера для одной строки
   fn capacity(&self) -> i32 {
     match *self {
-      Storage::Vec { capacity, .. } => capacity as i32,
+      Storage::Vec { elem_size, .. } => elem_size as i32,
+      // Для значений, извлекаемых по частям, указывается максимальный размер всего значения
+      Storage::Pieces { .. } => i32::MAX,
       _ => mem::size_of::<*const ()>() as i32,
     }
   }
-  /// Получает адрес в памяти, куда будет записан размер данных, фактически извлеченный из базы
-  fn size_mut(&mut self) -> *mut u16 {
+  /// Получает адрес в памяти, куда будут записаны размеры данных, фактически извлеченные из базы
+  fn sizes_mut(&mut self) -> *mut u16 {
     match *self {
-      Storage::Vec { ref mut size, .. } => size,
+      Storage::Vec { ref mut sizes, .. } => sizes.as_mut_ptr(),
       _ => ptr::null_mut(),
     }
   }
-  fn as_slice(&self) -> &[u8] {
+  fn as_slice(&self, index: usize) -> &[u8] {
     match *self {
-      Storage::Vec { ptr, size, .. } => unsafe { slice::from_raw_parts(ptr, size as usize) },
-      Storage::Descriptor(ref d) => d.as_slice(),
+      Storage::Vec { ref data, elem_size, ref sizes } => {
+        let start = index * elem_size;
+        &data[start..start + sizes[index] as usize]
+      },
+      Storage::Pieces { ref data, .. } => &data[..],
+      Storage::Descriptor { ref ptrs, .. } |
+      Storage::Handle { ref ptrs, .. } => unsafe {
+        slice::from_raw_parts(
+          &ptrs[index] as *const *const c_void as *const u8,
+          mem::size_of::<*const c_void>()
+        )
+      },
     }
   }
 }
-impl<'d> From<Vec<u8>> for Storage<'d> {
-  fn from(mut backend: Vec<u8>) -> Self {
-    let res = Storage::Vec { ptr: backend.as_mut_ptr(), size: 0, capacity: backend.capacity() };
-    // Вектор уходит в небытие, чтобы он не забрал память с собой, забываем его
-    mem::forget(backend);
-    res
+impl<'d> From<(usize, usize)> for Storage<'d> {
+  /// Создает хранилище для `count` значений по `elem_size` байт.
+  fn from((elem_size, count): (usize, usize)) -> Self {
+    Storage::Vec { data: vec![0; elem_size * count], elem_size: elem_size, sizes: vec![0; count] }
   }
 }
-impl<'d, T: DescriptorType> From<Descriptor<'d, T>> for Storage<'d> {
-  fn from(backend: Descriptor<'d, T>) -> Self {
-    Storage::Descriptor(backend.into())
+impl<'d, T: DescriptorType> From<Vec<Descriptor<'d, T>>> for Storage<'d> {
+  fn from(backend: Vec<Descriptor<'d, T>>) -> Self {
+    let descriptors: Vec<GenericDescriptor> = backend.into_iter().map(Into::into).collect();
+    let ptrs = descriptors.iter().map(|d| d.native::<T>().unwrap() as *const c_void).collect();
+    Storage::Descriptor { descriptors: descriptors, ptrs: ptrs, phantom: PhantomData }
   }
 }
-impl<'d> Drop for Storage<'d> {
-  fn drop(&mut self) {
-    // Освобождаем память деструктором вектора, ведь память была выделена его конструктором
-    if let Storage::Vec { ptr, capacity, size } = *self {
-      unsafe { Vec::from_raw_parts(ptr, size as usize, capacity) };
-    };
+impl<'d> From<Vec<Handle<OCIStmt>>> for Storage<'d> {
+  fn from(handles: Vec<Handle<OCIStmt>>) -> Self {
+    let ptrs = handles.iter().map(|h| h.native_mut() as *const c_void).collect();
+    Storage::Handle { handles: handles, ptrs: ptrs }
   }
 }
 
 macro_rules! alloc {
   (
-    $stmt:expr, $col:expr,
+    $stmt:expr, $col:expr, $count:expr,
     $($kind:ident, $ty:ty),*
   ) => (
     match $col.type_ {
       $(
         Type::$kind => {
-          let d: Descriptor<'d, $ty> = try!($stmt.conn.server.new_descriptor());
-          Ok(d.into())
+          let mut vec: Vec<Descriptor<$ty>> = Vec::with_capacity($count);
+          for _ in 0..$count {
+            vec.push(try!($stmt.conn.new_descriptor()));
+          }
+          Ok(DefineInfo::new_with(vec.into(), $count))
         }
       )*
-      _ => Ok(Vec::with_capacity($col.size as usize).into()),
+      _ => Ok(DefineInfo::new_with(($col.size as usize, $count).into(), $count)),
     }
   );
 }
-/// Хранилище буферов для биндинга результатов, извлекаемых из базы, для одной колонки
+/// Размер одной части значения, запрашиваемой у сервера при извлечении значений `LONG` и `LONG RAW` по частям,
+/// а также передаваемой серверу при потоковом связывании переменной из читателя.
+pub const PIECE_SIZE: usize = 64 * 1024;
+
+/// Проверяет, должны ли значения столбца указанного типа извлекаться с сервера по частям. Максимальный размер
+/// значений таких столбцов (2Гб) не позволяет заранее выделить под них буфер.
+#[allow(deprecated)]// Столбцы LONG все еще встречаются в старых схемах, поэтому поддерживаем их
+pub fn is_piecewise(ty: Type) -> bool {
+  match ty {
+    Type::LNG | Type::LBI => true,
+    _ => false,
+  }
+}
+
+/// Хранилище буферов для биндинга результатов, извлекаемых из базы, для одной колонки. Содержит место для
+/// значений нескольких строк, количество которых задается при создании.
 #[derive(Debug)]
 pub struct DefineInfo<'d> {
   storage: Storage<'d>,
-  /// Возможные значения:
+  /// Индикаторы для каждой строки. Возможные значения:
   /// * `-2`  The length of the item is greater than the length of the output variable; the item has been truncated. Additionally,
   ///         the original length is longer than the maximum data length that can be returned in the sb2 indicator variable.
   /// * `-1`  The selected value is null, and the value of the output variable is unchanged.
   /// * `0`   Oracle Database assigned an intact value to the host variable.
   /// * `>0`  The length of the item is greater than the length of the output variable; the item has been truncated. The positive
   ///         value returned in the indicator variable is the actual length before truncation.
-  pub is_null: i16,
-  pub ret_code: u16,
+  is_null: Vec<i16>,
+  /// Коды возврата для каждой строки.
+  ret_codes: Vec<u16>,
+  /// Хендл, созданный при связывании буферов со столбцом. Используется для определения, для какого столбца сервер
+  /// запрашивает очередную часть значения при извлечении по частям.
+  handle: *mut OCIDefine,
 }
 impl<'d> DefineInfo<'d> {
-  /// Создает буферы для хранения информации, извлекаемой из базы
-  pub fn new(stmt: &'d Statement, column: &Column) -> Result<Self> {
-    alloc!(stmt, column,
+  /// Создает буферы для хранения информации, извлекаемой из базы, для указанного количества строк
+  pub fn new(stmt: &'d Statement, column: &Column, count: usize) -> Result<Self> {
+    // Вложенные курсоры (`CURSOR(...)`) извлекаются в заранее выделенные хендлы выражений
+    if let Type::CUR | Type::RSET = column.type_ {
+      let mut vec = Vec::with_capacity(count);
+      for _ in 0..count {
+        vec.push(try!(stmt.conn.new_handle()));
+      }
+      return Ok(DefineInfo::new_with(vec.into(), count));
+    }
+    // Значения по частям извлекаются только для одной строки за раз, см. `RowSet::fetch_rows()`
+    if is_piecewise(column.type_) {
+      let mut info = DefineInfo::new_with(Storage::Pieces { data: Vec::new(), filled: 0, alen: 0 }, 1);
+      // Для `NULL`-значения сервер не запрашивает ни одной части, и индикатор остается неизменным
+      info.is_null[0] = OCIInd::Null as i16;
+      return Ok(info);
+    }
+    alloc!(stmt, column, count,
       TIMESTAMP, Timestamp,
       TIMESTAMP_TZ, TimestampWithTZ,
       TIMESTAMP_LTZ, TimestampWithLTZ,
@@ -128,6 +213,9 @@ impl<'d> DefineInfo<'d> {
       CFILEE, File
     )
   }
+  fn new_with(storage: Storage<'d>, count: usize) -> Self {
+    DefineInfo { storage: storage, is_null: vec![0; count], ret_codes: vec![0; count], handle: ptr::null_mut() }
+  }
   #[inline]
   pub fn as_ptr(&mut self) -> *mut c_void {
     self.storage.as_ptr()
@@ -137,38 +225,424 @@ impl<'d> DefineInfo<'d> {
     self.storage.capacity()
   }
   #[inline]
-  pub fn size_mut(&mut self) -> *mut u16 {
-    self.storage.size_mut()
+  pub fn is_null_mut(&mut self) -> *mut c_void {
+    self.is_null.as_mut_ptr() as *mut c_void
+  }
+  #[inline]
+  pub fn sizes_mut(&mut self) -> *mut u16 {
+    self.storage.sizes_mut()
+  }
+  #[inline]
+  pub fn ret_codes_mut(&mut self) -> *mut u16 {
+    self.ret_codes.as_mut_ptr()
+  }
+  #[inline]
+  pub fn handle(&self) -> *mut OCIDefine {
+    self.handle
+  }
+  #[inline]
+  pub fn set_handle(&mut self, handle: *mut OCIDefine) {
+    self.handle = handle;
+  }
+  /// Проверяет, извлекается ли значение данного столбца по частям.
+  #[inline]
+  pub fn is_piecewise(&self) -> bool {
+    match self.storage {
+      Storage::Pieces { .. } => true,
+      _ => false,
+    }
+  }
+  /// Выделяет место под очередную часть значения, извлекаемого по частям, и возвращает указатель на него и на
+  /// переменную с его размером, которые необходимо передать в `OCIStmtSetPieceInfo()`.
+  pub fn next_piece(&mut self) -> (*mut c_void, *mut u32) {
+    self.finish_pieces();
+    match self.storage {
+      Storage::Pieces { ref mut data, filled, ref mut alen } => {
+        data.resize(filled + PIECE_SIZE, 0);
+        *alen = PIECE_SIZE as u32;
+        (data[filled..].as_mut_ptr() as *mut c_void, alen as *mut u32)
+      },
+      _ => (ptr::null_mut(), ptr::null_mut()),
+    }
+  }
+  /// Учитывает данные, записанные сервером в последнюю запрошенную часть значения, и отбрасывает неиспользованную
+  /// память. Может вызываться многократно.
+  pub fn finish_pieces(&mut self) {
+    if let Storage::Pieces { ref mut data, ref mut filled, ref mut alen } = self.storage {
+      *filled += *alen as usize;
+      *alen = 0;
+      data.truncate(*filled);
+    }
+  }
+  /// Подготавливает буферы к повторному использованию при извлечении следующей порции строк: отбрасывает части
+  /// значения, накопленные при извлечении предыдущей порции, и сбрасывает их индикатор в `NULL`. Остальные данные
+  /// сервер перезапишет сам.
+  pub fn reset(&mut self) {
+    if let Storage::Pieces { ref mut data, ref mut filled, ref mut alen } = self.storage {
+      data.clear();
+      *filled = 0;
+      *alen = 0;
+      self.is_null[0] = OCIInd::Null as i16;
+    }
   }
 
-  /// Возвращает представление данного хранилища в виде среза из массива байт, если
+  /// Возвращает представление значения указанной строки данного хранилища в виде среза из массива байт, если
   /// в хранилище есть данные и `None`, если в хранилище хранится `NULL` значение.
   #[inline]
-  fn as_slice(&self) -> Option<&[u8]> {
-    match self.is_null {
-      0 => Some(self.storage.as_slice()),
+  fn as_slice(&self, index: usize) -> Option<&[u8]> {
+    match self.is_null[index] {
+      0 => Some(self.storage.as_slice(index)),
       _ => None
     }
   }
-  /// Представляет содержимое данного хранилища в виде объекта указанного типа
+  /// Получает хендл курсора, извлеченного в указанную строку данного хранилища, или `None`, если в ней хранится
+  /// `NULL` или хранилище не содержит курсоров.
+  pub fn cursor(&self, index: usize) -> Option<*mut OCIStmt> {
+    match (self.is_null[index], &self.storage) {
+      (0, &Storage::Handle { ref ptrs, .. }) => Some(ptrs[index] as *mut OCIStmt),
+      _ => None,
+    }
+  }
+  /// Представляет значение указанной строки данного хранилища в виде объекта указанного типа
   #[inline]
-  pub fn to<'c, T: FromDB<'c>>(&self, ty: Type, conn: &'c Connection) -> Result<Option<T>> {
-    match self.as_slice() {
+  pub fn to<'c, T: FromDB<'c>>(&self, index: usize, ty: Type, conn: &'c Connection) -> Result<Option<T>> {
+    match self.as_slice(index) {
       Some(ref slice) => T::from_db(ty, slice, conn).map(|r| Some(r)),
       None => Ok(None),
     }
   }
 }
-impl<'d> From<Vec<u8>> for DefineInfo<'d> {
-  fn from(backend: Vec<u8>) -> Self {
-    DefineInfo { storage: backend.into(), is_null: 0, ret_code: 0 }
+
+/// Хранилище данных для массивного связывания одной переменной: значения всех итераций выполнения выражения
+/// копируются в один непрерывный буфер, в котором каждое значение занимает одинаковое количество байт.
+#[derive(Debug)]
+pub struct BindArray {
+  /// Тип базы данных, в котором представлены значения массива.
+  pub ty: Type,
+  /// Буфер со значениями массива, выровненными по размеру максимального элемента.
+  data: Vec<u8>,
+  /// Размер, отводимый в буфере `data` под каждое значение массива.
+  elem_size: usize,
+  /// Массив индикаторов `NULL`-ов для каждого элемента.
+  is_null: Vec<OCIInd>,
+  /// Массив реальных длин для каждого элемента.
+  sizes: Vec<u16>,
+}
+impl BindArray {
+  /// Копирует значения из среза во внутренние буферы, пригодные для связывания в качестве массива.
+  ///
+  /// Возвращает ошибку, если размер одного из элементов не помещается в `u16`, т.к. именно такой размер имеют
+  /// элементы массива длин, передаваемого в OCI.
+  pub fn new<T: AsDB>(values: &[T]) -> Result<Self> {
+    let mut elem_size = 1;
+    for v in values {
+      if let Some(data) = v.as_db() {
+        if data.len() > u16::MAX as usize {
+          return Err(Error::Overflow { extracted: data.len(), capacity: u16::MAX as usize });
+        }
+        elem_size = cmp::max(elem_size, data.len());
+      }
+    }
+    let mut data = vec![0u8; elem_size * values.len()];
+    let mut is_null = Vec::with_capacity(values.len());
+    let mut sizes = Vec::with_capacity(values.len());
+    for (i, v) in values.iter().enumerate() {
+      match v.as_db() {
+        Some(slice) => {
+          data[i * elem_size..i * elem_size + slice.len()].copy_from_slice(slice);
+          is_null.push(OCIInd::NotNull);
+          sizes.push(slice.len() as u16);
+        },
+        None => {
+          is_null.push(OCIInd::Null);
+          sizes.push(0);
+        },
+      }
+    }
+    Ok(BindArray { ty: T::ty(), data: data, elem_size: elem_size, is_null: is_null, sizes: sizes })
+  }
+  /// Количество элементов в массиве.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.sizes.len()
+  }
+  /// Указатель на буфер с данными для передачи в `OCIBindByPos()`/`OCIBindByName()`.
+  #[inline]
+  pub fn as_ptr(&self) -> *mut c_void {
+    self.data.as_ptr() as *mut c_void
+  }
+  /// Размер одного элемента в буфере данных.
+  #[inline]
+  pub fn elem_size(&self) -> i32 {
+    self.elem_size as i32
   }
+  /// Указатель на массив индикаторов `NULL`-ов.
+  #[inline]
+  pub fn is_null_ptr(&self) -> *mut c_void {
+    self.is_null.as_ptr() as *mut c_void
+  }
+  /// Указатель на массив длин элементов.
+  #[inline]
+  pub fn sizes_ptr(&self) -> *mut u16 {
+    self.sizes.as_ptr() as *mut u16
+  }
+}
+
+/// Хранилище для входной переменной связывания, принадлежащее выражению. OCI читает значение и индикатор `NULL`-а
+/// не в момент связывания, а при выполнении выражения, а в PL/SQL блоках все переменные являются `IN OUT`, так что
+/// сервер может записать в них значение, поэтому память под них должна принадлежать выражению.
+#[derive(Debug)]
+pub struct InBind {
+  /// Копия значения переменной. Пуст, если значение находится в памяти приложения или является `NULL`-ом.
+  data: Vec<u8>,
+  /// Индикатор `NULL`-а. Хранится в куче, чтобы его адрес не менялся при перемещении объекта.
+  is_null: Vec<i16>,
 }
-impl<'d, T> From<Descriptor<'d, T>> for DefineInfo<'d>
-  where T: DescriptorType,
-        Storage<'d>: From<Descriptor<'d, T>>
-{
-  fn from(backend: Descriptor<'d, T>) -> Self {
-    DefineInfo { storage: backend.into(), is_null: 0, ret_code: 0 }
+impl InBind {
+  /// Создает хранилище только для индикатора `NULL`-а значения, данные которого остаются в памяти приложения.
+  pub fn borrowed(info: &BindInfo) -> Self {
+    InBind { data: Vec::new(), is_null: vec![info.is_null as i16] }
   }
-}
\ No newline at end of file
+  /// Создает хранилище с копией значения и его индикатора `NULL`-а.
+  pub fn copied(info: &BindInfo) -> Self {
+    let data = match info.is_null {
+      OCIInd::Null => Vec::new(),
+      _ => unsafe { slice::from_raw_parts(info.ptr as *const u8, info.size) }.to_vec(),
+    };
+    InBind { data: data, is_null: vec![info.is_null as i16] }
+  }
+  /// Указатель на копию значения для передачи в `OCIBindByPos()`/`OCIBindByName()`, или нулевой указатель, если
+  /// копии нет.
+  #[inline]
+  pub fn as_ptr(&mut self) -> *mut c_void {
+    if self.data.is_empty() {
+      ptr::null_mut()
+    } else {
+      self.data.as_mut_ptr() as *mut c_void
+    }
+  }
+  /// Указатель на индикатор `NULL`-а.
+  #[inline]
+  pub fn is_null_mut(&mut self) -> *mut c_void {
+    self.is_null.as_mut_ptr() as *mut c_void
+  }
+}
+
+/// Максимальный размер буфера для выходного параметра переменной длины, равный максимальной длине строки
+/// `VARCHAR2` в PL/SQL.
+const MAX_OUT_SIZE: usize = 32767;
+
+/// Вычисляет размер буфера, необходимого для приема значения указанного типа из базы. Для числовых типов размер
+/// буфера равен размеру типа Rust, для остальных используется максимально возможный для PL/SQL размер.
+pub fn out_size<T: AsDB>() -> usize {
+  match T::ty() {
+    Type::INT | Type::UIN | Type::FLT |
+    Type::BFLOAT | Type::BDOUBLE |
+    Type::IBFLOAT | Type::IBDOUBLE | Type::BOL => mem::size_of::<T>(),
+    _ => MAX_OUT_SIZE,
+  }
+}
+
+/// Хранилище данных для выходной (`OUT` или `IN OUT`) переменной связывания, в которое сервер запишет значение
+/// при выполнении выражения.
+#[derive(Debug)]
+pub struct OutBind {
+  /// Тип базы данных, в котором сервер записывает значение переменной.
+  pub ty: Type,
+  /// Буфер, в который сервер записывает значение переменной.
+  data: Vec<u8>,
+  /// Индикатор `NULL`-а значения. Хранится в куче, чтобы его адрес не менялся при перемещении объекта.
+  is_null: Vec<i16>,
+  /// Реальная длина значения, записанного в буфер. Хранится в куче, чтобы ее адрес не менялся при перемещении объекта.
+  sizes: Vec<u16>,
+  /// Хендл выражения, в который сервер откроет курсор, если переменная имеет тип `SYS_REFCURSOR`.
+  cursor: Option<Handle<OCIStmt>>,
+}
+impl OutBind {
+  /// Создает буфер для приема значения указанного типа, изначально содержащий `NULL`.
+  pub fn new<T: AsDB>() -> Self {
+    OutBind { ty: T::ty(), data: vec![0; out_size::<T>()], is_null: vec![OCIInd::Null as i16], sizes: vec![0], cursor: None }
+  }
+  /// Создает буфер для приема курсора (`SYS_REFCURSOR`), который сервер откроет в указанном хендле выражения.
+  pub fn cursor(handle: Handle<OCIStmt>) -> Self {
+    // Для курсоров в качестве буфера передается адрес переменной, содержащей указатель на хендл
+    let ptr = handle.native_mut() as usize;
+    let data = unsafe { slice::from_raw_parts(&ptr as *const usize as *const u8, mem::size_of::<usize>()) }.to_vec();
+    OutBind { ty: Type::RSET, data: data, is_null: vec![OCIInd::Null as i16], sizes: vec![0], cursor: Some(handle) }
+  }
+  /// Создает буфер для приема значения указанного типа, изначально содержащий переданное значение.
+  ///
+  /// Возвращает ошибку, если размер значения не помещается в `u16`, т.к. именно такой размер имеет
+  /// длина, передаваемая в OCI.
+  pub fn with_value<T: AsDB>(value: &T) -> Result<Self> {
+    let mut out = Self::new::<T>();
+    if let Some(slice) = value.as_db() {
+      if slice.len() > u16::MAX as usize {
+        return Err(Error::Overflow { extracted: slice.len(), capacity: u16::MAX as usize });
+      }
+      if slice.len() > out.data.len() {
+        out.data.resize(slice.len(), 0);
+      }
+      out.data[..slice.len()].copy_from_slice(slice);
+      out.is_null[0] = OCIInd::NotNull as i16;
+      out.sizes[0] = slice.len() as u16;
+    }
+    Ok(out)
+  }
+  /// Указатель на буфер для передачи в `OCIBindByPos()`/`OCIBindByName()`.
+  #[inline]
+  pub fn as_ptr(&mut self) -> *mut c_void {
+    self.data.as_mut_ptr() as *mut c_void
+  }
+  /// Вместимость буфера.
+  #[inline]
+  pub fn capacity(&self) -> i32 {
+    self.data.len() as i32
+  }
+  /// Указатель на индикатор `NULL`-а.
+  #[inline]
+  pub fn is_null_mut(&mut self) -> *mut c_void {
+    self.is_null.as_mut_ptr() as *mut c_void
+  }
+  /// Указатель на длину значения. Для курсоров длина не используется.
+  #[inline]
+  pub fn sizes_mut(&mut self) -> *mut u16 {
+    match self.cursor {
+      Some(_) => ptr::null_mut(),
+      None => self.sizes.as_mut_ptr(),
+    }
+  }
+  /// Представляет значение, записанное сервером в буфер, в виде объекта указанного типа.
+  pub fn to<'c, T: FromDB<'c>>(&self, conn: &'c Connection) -> Result<Option<T>> {
+    match self.is_null[0] {
+      0 => T::from_db(self.ty, &self.data[..self.sizes[0] as usize], conn).map(|r| Some(r)),
+      _ => Ok(None),
+    }
+  }
+  /// Получает хендл, в котором сервер открыл курсор, или `None`, если сервер записал в переменную `NULL` или
+  /// переменная не является курсором.
+  pub fn cursor_handle(&self) -> Option<*mut OCIStmt> {
+    match (self.is_null[0], &self.cursor) {
+      (0, &Some(ref handle)) => Some(handle.native_mut()),
+      _ => None,
+    }
+  }
+}
+
+/// Хранилище данных для переменной связывания, являющейся PL/SQL массивом (`TABLE OF ... INDEX BY ...`). В отличие от
+/// [`BindArray`][1], весь массив передается как одно значение за одну итерацию выполнения выражения, а сервер может
+/// изменить как значения элементов, так и их количество.
+///
+/// [1]: struct.BindArray.html
+#[derive(Debug)]
+pub struct BindTable {
+  /// Тип базы данных, в котором представлены элементы массива.
+  pub ty: Type,
+  /// Буфер со значениями элементов, выровненными по размеру максимального элемента.
+  data: Vec<u8>,
+  /// Размер, отводимый в буфере `data` под каждый элемент.
+  elem_size: usize,
+  /// Массив индикаторов `NULL`-ов для каждого элемента.
+  is_null: Vec<i16>,
+  /// Массив реальных длин для каждого элемента.
+  sizes: Vec<u16>,
+  /// Текущее количество элементов в массиве. Хранится в куче, чтобы его адрес не менялся при перемещении объекта.
+  len: Vec<u32>,
+}
+impl BindTable {
+  /// Создает пустой массив, в который сервер может записать до `capacity` элементов. Размер элементов определяется
+  /// так же, как для выходных переменных в функции [`out_size()`][1].
+  ///
+  /// [1]: fn.out_size.html
+  pub fn new<T: AsDB>(capacity: usize) -> Self {
+    let elem_size = out_size::<T>();
+    BindTable {
+      ty: T::ty(),
+      data: vec![0; elem_size * capacity],
+      elem_size: elem_size,
+      is_null: vec![OCIInd::Null as i16; capacity],
+      sizes: vec![0; capacity],
+      len: vec![0],
+    }
+  }
+  /// Копирует значения из среза во внутренние буферы. Вместимость массива будет не меньше `capacity` и не меньше
+  /// количества переданных значений. Если `out` равен `true`, под каждый элемент резервируется место для значения,
+  /// которое может записать сервер, иначе -- только под самое длинное из переданных значений.
+  ///
+  /// Возвращает ошибку, если размер одного из элементов не помещается в `u16`, т.к. именно такой размер имеют
+  /// элементы массива длин, передаваемого в OCI.
+  pub fn with_values<T: AsDB>(values: &[T], capacity: usize, out: bool) -> Result<Self> {
+    let mut elem_size = if out { out_size::<T>() } else { 1 };
+    for v in values {
+      if let Some(data) = v.as_db() {
+        if data.len() > u16::MAX as usize {
+          return Err(Error::Overflow { extracted: data.len(), capacity: u16::MAX as usize });
+        }
+        elem_size = cmp::max(elem_size, data.len());
+      }
+    }
+    let capacity = cmp::max(capacity, values.len());
+    let mut table = BindTable {
+      ty: T::ty(),
+      data: vec![0; elem_size * capacity],
+      elem_size: elem_size,
+      is_null: vec![OCIInd::Null as i16; capacity],
+      sizes: vec![0; capacity],
+      len: vec![values.len() as u32],
+    };
+    for (i, v) in values.iter().enumerate() {
+      if let Some(slice) = v.as_db() {
+        table.data[i * elem_size..i * elem_size + slice.len()].copy_from_slice(slice);
+        table.is_null[i] = OCIInd::NotNull as i16;
+        table.sizes[i] = slice.len() as u16;
+      }
+    }
+    Ok(table)
+  }
+  /// Максимальное количество элементов, которое может содержать массив.
+  #[inline]
+  pub fn capacity(&self) -> u32 {
+    self.sizes.len() as u32
+  }
+  /// Указатель на буфер с данными для передачи в `OCIBindByPos()`/`OCIBindByName()`.
+  #[inline]
+  pub fn as_ptr(&mut self) -> *mut c_void {
+    self.data.as_mut_ptr() as *mut c_void
+  }
+  /// Размер одного элемента в буфере данных.
+  #[inline]
+  pub fn elem_size(&self) -> i32 {
+    self.elem_size as i32
+  }
+  /// Указатель на массив индикаторов `NULL`-ов.
+  #[inline]
+  pub fn is_null_mut(&mut self) -> *mut c_void {
+    self.is_null.as_mut_ptr() as *mut c_void
+  }
+  /// Указатель на массив длин элементов.
+  #[inline]
+  pub fn sizes_mut(&mut self) -> *mut u16 {
+    self.sizes.as_mut_ptr()
+  }
+  /// Указатель на текущее количество элементов в массиве.
+  #[inline]
+  pub fn len_mut(&mut self) -> *mut u32 {
+    self.len.as_mut_ptr()
+  }
+  /// Представляет элементы массива, записанные сервером, в виде объектов указанного типа.
+  pub fn to<'c, T: FromDB<'c>>(&self, conn: &'c Connection) -> Result<Vec<Option<T>>> {
+    let len = cmp::min(self.len[0] as usize, self.sizes.len());
+    let mut result = Vec::with_capacity(len);
+    for i in 0..len {
+      let value = match self.is_null[i] {
+        0 => {
+          let start = i * self.elem_size;
+          Some(try!(T::from_db(self.ty, &self.data[start..start + self.sizes[i] as usize], conn)))
+        },
+        _ => None,
+      };
+      result.push(value);
+    }
+    Ok(result)
+  }
+}
@@ -0,0 +1,135 @@
+//! Содержит типы для явного управления транзакциями.
+
+use {Connection, Result};
+use error::Error;
+
+/// Проверяет, что указанное имя является корректным идентификатором Oracle без кавычек и может быть безопасно
+/// подставлено в текст запроса. Идентификатор должен начинаться с буквы и содержать только буквы, цифры и символы
+/// `_`, `$` и `#`, а его длина не должна превышать 128 байт.
+fn check_identifier(name: &str) -> Result<()> {
+  let valid = name.len() <= 128
+           && name.chars().next().map_or(false, |c| c.is_ascii_alphabetic())
+           && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '#');
+  match valid {
+    true => Ok(()),
+    false => Err(Error::InvalidIdentifier(name.to_owned())),
+  }
+}
+
+/// Транзакция, автоматически откатываемая при выходе из области видимости, если она не была явно
+/// [зафиксирована][commit]. Создается вызовом метода [`Connection::transaction()`][new].
+///
+/// Oracle начинает транзакцию неявно при выполнении первого изменяющего данные выражения, поэтому создание
+/// данного объекта не приводит к запросам к серверу. Объект не отделяет свои изменения от сделанных ранее: при
+/// разрушении без явной фиксации откатывается вся незафиксированная транзакция соединения, включая изменения,
+/// сделанные до создания объекта. Откат происходит в том числе и при панике.
+///
+/// Пока объект существует, соединение не позволяет создать еще одну транзакцию и зафиксировать или откатить
+/// транзакцию в обход данного объекта.
+///
+/// # Пример
+/// ```
+/// # use oci::Environment;
+/// # use oci::params::{ConnectParams, Credentials};
+/// # let env = Environment::default();
+/// # let conn = env.connect(ConnectParams { dblink: "".into(), attach_mode: Default::default(), credentials: Credentials::Ext, auth_mode: Default::default() }).unwrap();
+/// {
+///   let tx = conn.transaction().unwrap();
+///   conn.prepare("insert into test_bind (id) values (10)").unwrap().execute().unwrap();
+///   tx.savepoint("after_insert").unwrap();
+///   conn.prepare("delete from test_bind").unwrap().execute().unwrap();
+///   // Отменяем удаление, но сохраняем вставку
+///   tx.rollback_to("after_insert").unwrap();
+///   // Вызов `tx.commit()` зафиксировал бы вставку, но его нет, поэтому
+///   // при выходе из области видимости транзакция будет откачена
+/// }
+/// ```
+///
+/// [commit]: #method.commit
+/// [new]: ../struct.Connection.html#method.transaction
+#[derive(Debug)]
+pub struct Transaction<'conn> {
+  /// Соединение, в котором выполняется транзакция
+  conn: &'conn Connection<'conn>,
+  /// Признак того, что транзакция уже была зафиксирована или откачена и ее не нужно откатывать при разрушении.
+  finished: bool,
+}
+impl<'conn> Transaction<'conn> {
+  /// Получает соединение, в котором выполняется данная транзакция.
+  #[inline]
+  pub fn connection(&self) -> &'conn Connection<'conn> {
+    self.conn
+  }
+  /// Фиксирует все незафиксированные изменения соединения. Если зафиксировать изменения не удалось, транзакция
+  /// откатывается при разрушении объекта, как если бы `commit()` не вызывался.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCITransCommit()`][1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: http://docs.oracle.com/database/122/LNOCI/transaction-functions.htm#LNOCI17197
+  pub fn commit(mut self) -> Result<()> {
+    try!(self.conn.commit_impl());
+    // Только после успешного завершения, иначе при разрушении транзакция будет откачена
+    self.finished = true;
+    Ok(())
+  }
+  /// Откатывает все незафиксированные изменения соединения.
+  ///
+  /// # OCI вызовы
+  /// Выполняет OCI вызов [`OCITransRollback()`][1].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет один запрос к серверу.
+  ///
+  /// [1]: http://docs.oracle.com/database/122/LNOCI/transaction-functions.htm#LNOCI17204
+  pub fn rollback(mut self) -> Result<()> {
+    try!(self.conn.rollback_impl());
+    // Только после успешного завершения, иначе при разрушении транзакция будет откачена
+    self.finished = true;
+    Ok(())
+  }
+  /// Создает в транзакции точку сохранения с указанным именем, до которой затем можно будет откатить изменения
+  /// вызовом [`rollback_to()`][1]. Если точка сохранения с таким именем уже существует, она перемещается в текущее
+  /// место транзакции.
+  ///
+  /// Имя должно быть корректным идентификатором Oracle без кавычек, в противном случае возвращается ошибка
+  /// [`InvalidIdentifier`][err].
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет выражение `SAVEPOINT`, что требует одного запроса к серверу.
+  ///
+  /// [1]: #method.rollback_to
+  /// [err]: ../error/enum.Error.html#variant.InvalidIdentifier
+  pub fn savepoint(&self, name: &str) -> Result<()> {
+    try!(check_identifier(name));
+    try!(try!(self.conn.prepare(&format!("savepoint {}", name))).execute());
+    Ok(())
+  }
+  /// Откатывает изменения, сделанные после создания точки сохранения с указанным именем. Сама транзакция при этом
+  /// остается активной, а точки сохранения, созданные после указанной, удаляются.
+  ///
+  /// # Запросы к серверу (1)
+  /// Функция выполняет выражение `ROLLBACK TO SAVEPOINT`, что требует одного запроса к серверу.
+  pub fn rollback_to(&self, name: &str) -> Result<()> {
+    try!(check_identifier(name));
+    try!(try!(self.conn.prepare(&format!("rollback to savepoint {}", name))).execute());
+    Ok(())
+  }
+}
+impl<'conn> Drop for Transaction<'conn> {
+  fn drop(&mut self) {
+    if !self.finished {
+      // Невозможно делать панику отсюда, т.к. приложение из-за этого крашится
+      let _ = self.conn.rollback_impl();
+    }
+    self.conn.transaction.set(false);
+  }
+}
+impl<'conn> super::TransactionPrivate<'conn> for Transaction<'conn> {
+  fn new(conn: &'conn Connection<'conn>) -> Self {
+    Transaction { conn: conn, finished: false }
+  }
+}
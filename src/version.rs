@@ -14,8 +14,8 @@ pub enum ParseVersionError {
   /// Количество цифр версии, разделенных точкой, превышает 5 штук.
   Count,
 }
-/// Описывает версию клиента или сервера
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Описывает версию клиента или сервера. Версии сравниваются по полям в порядке их объявления.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Version {
   /// Старшая версия сервера или клиента. Для релиза `12.2с` это 12.
   pub major: i32,
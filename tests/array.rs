@@ -0,0 +1,133 @@
+//! Тесты массивного выполнения выражений.
+
+extern crate oci;
+
+use oci::Environment;
+use oci::error::Error;
+use oci::types::CreateMode;
+mod utils;
+
+#[test]
+fn execute_many() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_array (id number, name varchar2(10))").unwrap().execute().expect("Can't create table");
+
+  {
+    let mut stmt = conn.prepare("insert into test_array values (:1, :2)").expect("Can't prepare INSERT");
+    stmt.bind_array(0, &[1i32, 2, 3, 4]).expect("Can't bind ids");
+    stmt.bind_array(1, &[Some("one"), None, Some("three"), Some("four")]).expect("Can't bind names");
+
+    // Выполняются только первые 3 элемента массивов
+    assert_eq!(3, stmt.execute_many(3).expect("Can't execute INSERT"));
+    assert_eq!(3, utils::count(&conn, "test_array"));
+
+    match stmt.execute_many(5) {
+      Err(Error::ArrayTooShort { iters: 5, len: 4 }) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+    assert_eq!(0, stmt.execute_many(0).expect("Can't execute INSERT with no iterations"));
+
+    // Повторно связанный массив заменяет предыдущий, поэтому более длинный старый массив не учитывается
+    stmt.bind_array(0, &[5i32]).expect("Can't rebind ids");
+    match stmt.execute_many(2) {
+      Err(Error::ArrayTooShort { iters: 2, len: 1 }) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+  }
+  {
+    // Выходная переменная связана с буфером из одного элемента, поэтому массивное выполнение запрещено
+    let mut stmt = conn.prepare("insert into test_array values (:id, 'out') returning id into :out").expect("Can't prepare INSERT");
+    stmt.bind_array(":id", &[10i32, 11]).expect("Can't bind ids");
+    stmt.bind_out::<i32, _>(":out").expect("Can't bind out parameter");
+    match stmt.execute_many(2) {
+      Err(Error::ArrayTooShort { iters: 2, len: 1 }) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+    assert_eq!(3, utils::count(&conn, "test_array"));
+  }
+  {
+    let mut stmt = conn.prepare("delete from test_array where id = :id").expect("Can't prepare DELETE");
+    stmt.bind_array(":id", &[1i32, 3, 10]).expect("Can't bind ids");
+    assert_eq!(2, stmt.execute_many(3).expect("Can't execute DELETE"));
+  }
+  assert_eq!(1, utils::count(&conn, "test_array"));
+
+  conn.prepare("drop table test_array").unwrap().execute().expect("Can't drop table");
+}
+
+#[test]
+fn execute_batch() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_batch (id number primary key)").unwrap().execute().expect("Can't create table");
+
+  {
+    let mut stmt = conn.prepare("insert into test_batch values (:1)").expect("Can't prepare INSERT");
+    stmt.bind_array(0, &[1i32, 2, 1, 3, 2]).expect("Can't bind ids");
+
+    let res = stmt.execute_batch(5).expect("Can't execute INSERT in batch mode");
+    assert!(!res.is_ok());
+    assert_eq!(3, res.total());
+
+    let offsets: Vec<_> = res.errors.iter().map(|e| e.offset).collect();
+    assert_eq!(vec![2, 4], offsets);
+    // ORA-00001: unique constraint violated
+    assert!(res.errors.iter().all(|e| e.info.code == 1));
+
+    // Количества строк по итерациям поддерживаются только начиная с 12.1
+    if utils::supports(&conn, 12, 1) {
+      assert_eq!(vec![1, 1, 0, 1, 0], res.row_counts);
+    } else {
+      assert!(res.row_counts.is_empty());
+    }
+  }
+  {
+    // Выходная переменная связана с буфером из одного элемента, поэтому массивное выполнение запрещено
+    let mut stmt = conn.prepare("insert into test_batch values (:id) returning id into :out").expect("Can't prepare INSERT");
+    stmt.bind_array(":id", &[10i32, 11]).expect("Can't bind ids");
+    stmt.bind_out::<i32, _>(":out").expect("Can't bind out parameter");
+    match stmt.execute_batch(2) {
+      Err(Error::ArrayTooShort { iters: 2, len: 1 }) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+    assert_eq!(3, utils::count(&conn, "test_batch"));
+  }
+
+  conn.prepare("drop table test_batch").unwrap().execute().expect("Can't drop table");
+}
+
+#[test]
+fn returning() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_returning (id number, grp number, name varchar2(10))").unwrap().execute().expect("Can't create table");
+
+  {
+    let mut stmt = conn.prepare("insert into test_returning values (:1, :2, :3) returning id, name into :4, :5").expect("Can't prepare INSERT");
+    stmt.bind_array(0, &[1i32, 2, 3]).expect("Can't bind ids");
+    stmt.bind_array(1, &[1i32, 1, 2]).expect("Can't bind groups");
+    stmt.bind_array(2, &[Some("one"), None, Some("three")]).expect("Can't bind names");
+    let id = stmt.bind_returning::<i32, _>(3).expect("Can't bind returning id");
+    let name = stmt.bind_returning::<String, _>(4).expect("Can't bind returning name");
+
+    assert_eq!(3, stmt.execute_many(3).expect("Can't execute INSERT"));
+    assert_eq!(vec![Some(2)], stmt.get_returning(&id, 1).expect("Can't get returning id"));
+    assert_eq!(vec![None], stmt.get_returning(&name, 1).expect("Can't get returning name"));
+    assert_eq!(vec![Some("three".to_owned())], stmt.get_returning(&name, 2).expect("Can't get returning name"));
+  }
+  {
+    let mut stmt = conn.prepare("update test_returning set id = id * 10 where grp = :grp returning id into :id").expect("Can't prepare UPDATE");
+    stmt.bind_array(":grp", &[1i32, 5]).expect("Can't bind groups");
+    let id = stmt.bind_returning::<i32, _>(":id").expect("Can't bind returning id");
+
+    assert_eq!(2, stmt.execute_many(2).expect("Can't execute UPDATE"));
+    let mut ids = stmt.get_returning(&id, 0).expect("Can't get returning ids");
+    ids.sort();
+    assert_eq!(vec![Some(10), Some(20)], ids);
+    // Итерация, не затронувшая ни одной строки, не возвращает значений
+    assert!(stmt.get_returning(&id, 1).expect("Can't get returning ids").is_empty());
+  }
+
+  conn.prepare("drop table test_returning").unwrap().execute().expect("Can't drop table");
+}
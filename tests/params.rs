@@ -0,0 +1,103 @@
+//! Тесты разбора строк подключения к базе данных и их обратного преобразования в строку.
+
+extern crate oci;
+
+use oci::params::{ConnectParams, Credentials, ParseConnectParamsError};
+use oci::types::AuthMode;
+
+fn rdbms(username: &str, password: &str) -> Credentials {
+  Credentials::Rdbms { username: username.into(), password: password.into() }
+}
+fn parse(s: &str) -> ConnectParams {
+  s.parse().expect(&format!("Can't parse connect string {:?}", s))
+}
+/// Проверяет, что строковое представление параметров разбирается в те же самые параметры
+fn roundtrip(params: ConnectParams) {
+  let s = params.to_string();
+  assert_eq!(params, parse(&s), "Connect string: {:?}", s);
+}
+
+#[test]
+fn ezconnect() {
+  let p = parse("scott/tiger@db.example.com:1521/orcl");
+  assert_eq!(rdbms("scott", "tiger"), p.credentials);
+  assert_eq!("db.example.com:1521/orcl", p.dblink);
+  assert_eq!(AuthMode::Default, p.auth_mode);
+  roundtrip(p);
+
+  let p = parse("scott/tiger@//db.example.com/orcl:dedicated");
+  assert_eq!(rdbms("scott", "tiger"), p.credentials);
+  assert_eq!("//db.example.com/orcl:dedicated", p.dblink);
+  roundtrip(p);
+}
+#[test]
+fn tns_descriptor() {
+  let tns = "(DESCRIPTION=(ADDRESS=(PROTOCOL=TCP)(HOST=db)(PORT=1521))(CONNECT_DATA=(SERVICE_NAME=orcl)))";
+  let p = parse(&format!("scott/tiger@{}", tns));
+  assert_eq!(tns, p.dblink);
+  roundtrip(p);
+
+  assert_eq!(Err(ParseConnectParamsError::UnbalancedParens), "scott/tiger@(DESCRIPTION=(ADDRESS=".parse::<ConnectParams>());
+}
+#[test]
+fn jdbc() {
+  let p = parse("jdbc:oracle:thin:@db:1521:orcl");
+  assert_eq!(Credentials::Ext, p.credentials);
+  assert_eq!("(DESCRIPTION=(ADDRESS=(PROTOCOL=TCP)(HOST=db)(PORT=1521))(CONNECT_DATA=(SID=orcl)))", p.dblink);
+  roundtrip(p);
+
+  let p = parse("jdbc:oracle:thin:scott/tiger@//db:1521/orcl");
+  assert_eq!(rdbms("scott", "tiger"), p.credentials);
+  assert_eq!("//db:1521/orcl", p.dblink);
+  roundtrip(p);
+
+  assert!(match "jdbc:oracle:thin:@db:port:orcl".parse::<ConnectParams>() {
+    Err(ParseConnectParamsError::InvalidPort(_)) => true,
+    _ => false,
+  });
+}
+#[test]
+fn external_auth() {
+  let p = parse("/ as sysdba");
+  assert_eq!(Credentials::Ext, p.credentials);
+  assert_eq!("", p.dblink);
+  assert_eq!(AuthMode::SysDba, p.auth_mode);
+  assert_eq!("/ as sysdba", p.to_string());
+
+  let p = parse("/@orcl AS SYSOPER");
+  assert_eq!(Credentials::Ext, p.credentials);
+  assert_eq!("orcl", p.dblink);
+  assert_eq!(AuthMode::SysOper, p.auth_mode);
+  roundtrip(p);
+
+  assert_eq!(Err(ParseConnectParamsError::UnknownAuthMode("sysasm".into())), "/ as sysasm".parse::<ConnectParams>());
+}
+#[test]
+fn quoted_credentials() {
+  let p = parse(r#"scott/"p@ss/word"@orcl"#);
+  assert_eq!(rdbms("scott", "p@ss/word"), p.credentials);
+  assert_eq!("orcl", p.dblink);
+  roundtrip(p);
+
+  assert_eq!(Err(ParseConnectParamsError::UnclosedQuote), r#"scott/"tiger@orcl"#.parse::<ConnectParams>());
+}
+#[test]
+fn escaped_quotes() {
+  let p = parse(r#"scott/"ti""ger"@orcl"#);
+  assert_eq!(rdbms("scott", r#"ti"ger"#), p.credentials);
+  assert_eq!(r#"scott/"ti""ger"@orcl"#, p.to_string());
+  roundtrip(p);
+
+  roundtrip(ConnectParams {
+    dblink: "orcl".into(),
+    attach_mode: Default::default(),
+    credentials: rdbms(r#""scott""#, r#"p"a/s"#),
+    auth_mode: AuthMode::SysDba,
+  });
+}
+#[test]
+fn invalid() {
+  assert_eq!(Err(ParseConnectParamsError::Empty), "  ".parse::<ConnectParams>());
+  assert_eq!(Err(ParseConnectParamsError::NoPassword), "scott@orcl".parse::<ConnectParams>());
+  assert_eq!(Err(ParseConnectParamsError::NoUsername), "/tiger@orcl".parse::<ConnectParams>());
+}
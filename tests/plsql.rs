@@ -0,0 +1,218 @@
+//! Тесты вызова PL/SQL блоков и хранимых процедур.
+
+extern crate oci;
+
+use oci::Environment;
+use oci::error::Error;
+use oci::types::CreateMode;
+mod utils;
+
+#[test]
+fn out_params() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+
+  let mut stmt = conn.prepare("begin :sum := :a + :b; :text := 'sum is ' || :sum; :null := null; :x := :x * 2; end;").expect("Can't prepare PL/SQL block");
+  unsafe {
+    stmt.bind(":a", &2i32).expect("Can't bind :a");
+    stmt.bind(":b", &3i32).expect("Can't bind :b");
+  }
+  let sum  = stmt.bind_out::<i64, _>(":sum").expect("Can't bind :sum");
+  let text = stmt.bind_out::<String, _>(":text").expect("Can't bind :text");
+  let null = stmt.bind_out::<i32, _>(":null").expect("Can't bind :null");
+  let x    = stmt.bind_inout(":x", &21u32).expect("Can't bind :x");
+  stmt.execute().expect("Can't execute PL/SQL block");
+
+  assert_eq!(Some(5), stmt.get_out(&sum).expect("Can't get :sum"));
+  assert_eq!(Some("sum is 5".to_owned()), stmt.get_out(&text).expect("Can't get :text"));
+  assert_eq!(None, stmt.get_out(&null).expect("Can't get :null"));
+  assert_eq!(Some(42), stmt.get_out(&x).expect("Can't get :x"));
+
+  // Описатель переменной другого выражения не принимается
+  let other = conn.prepare("begin :sum := 1; end;").expect("Can't prepare PL/SQL block");
+  match other.get_out(&sum) {
+    Err(Error::InvalidParam) => {},
+    r => panic!("Unexpected result: {:?}", r),
+  }
+}
+
+#[test]
+fn ref_cursor() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+
+  let mut stmt = conn.prepare("begin open :cur for select level, 'row ' || level from dual connect by level <= 3; end;").expect("Can't prepare PL/SQL block");
+  let cur = stmt.bind_cursor(":cur").expect("Can't bind :cur");
+  stmt.execute().expect("Can't execute PL/SQL block");
+
+  let mut cursor = stmt.get_cursor(&cur).expect("Can't get :cur").expect(":cur is NULL");
+  let rs = cursor.query().expect("Can't get rows from cursor");
+  let rows: Vec<(Option<u32>, Option<String>)> = (&rs).map(|r| (r.get(0).unwrap(), r.get(1).unwrap())).collect();
+  assert_eq!(vec![
+    (Some(1), Some("row 1".to_owned())),
+    (Some(2), Some("row 2".to_owned())),
+    (Some(3), Some("row 3".to_owned())),
+  ], rows);
+}
+
+#[test]
+fn implicit_results() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+  // `DBMS_SQL.RETURN_RESULT` и `OCIStmtGetNextResult()` появились только в 12.1
+  if !utils::supports(&conn, 12, 1) {
+    return;
+  }
+
+  let mut stmt = conn.prepare("
+    declare
+      c1 sys_refcursor;
+      c2 sys_refcursor;
+    begin
+      open c1 for select level as num from dual connect by level <= 2;
+      dbms_sql.return_result(c1);
+      open c2 for select 'a' as str, 'b' as str2 from dual;
+      dbms_sql.return_result(c2);
+    end;").expect("Can't prepare PL/SQL block");
+  stmt.execute().expect("Can't execute PL/SQL block");
+
+  let mut results = stmt.implicit_results();
+  {
+    let mut first = results.next().expect("No first result").expect("Can't get first result");
+    let rs = first.query().expect("Can't get rows from first result");
+    assert_eq!(1, rs.columns().len());
+    assert_eq!("NUM", rs.columns()[0].name);
+    let values: Vec<Option<u32>> = (&rs).map(|r| r.get(0).unwrap()).collect();
+    assert_eq!(vec![Some(1), Some(2)], values);
+  }
+  {
+    let mut second = results.next().expect("No second result").expect("Can't get second result");
+    let rs = second.query().expect("Can't get rows from second result");
+    assert_eq!(2, rs.columns().len());
+    let row = rs.next().expect("Can't fetch").expect("Nothing fetch");
+    assert_eq!(Some("b".to_owned()), row.get("STR2").expect("Can't get STR2"));
+  }
+  assert!(results.next().is_none());
+}
+
+#[test]
+fn table_params() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+
+  let mut stmt = conn.prepare("
+    declare
+      type t_numbers is table of number index by pls_integer;
+      type t_names is table of varchar2(10) index by pls_integer;
+      a t_numbers := :a;
+      n t_names;
+      x t_numbers := :x;
+    begin
+      :sum := 0;
+      for i in 1..a.count loop :sum := :sum + nvl(a(i), 0); end loop;
+      for i in 1..3 loop n(i) := 'name ' || i; end loop;
+      :n := n;
+      for i in 1..x.count loop x(i) := x(i) * 2; end loop;
+      x(x.count + 1) := null;
+      :x := x;
+    end;").expect("Can't prepare PL/SQL block");
+  stmt.bind_table(":a", &[Some(1), None, Some(3), Some(4)]).expect("Can't bind :a");
+  let sum = stmt.bind_out::<i32, _>(":sum").expect("Can't bind :sum");
+  let n   = stmt.bind_table_out::<String, _>(":n", 5).expect("Can't bind :n");
+  let x   = stmt.bind_table_inout(":x", &[10, 21], 5).expect("Can't bind :x");
+  stmt.execute().expect("Can't execute PL/SQL block");
+
+  assert_eq!(Some(8), stmt.get_out(&sum).expect("Can't get :sum"));
+  assert_eq!(vec![Some("name 1".to_owned()), Some("name 2".to_owned()), Some("name 3".to_owned())], stmt.get_table(&n).expect("Can't get :n"));
+  assert_eq!(vec![Some(20), Some(42), None], stmt.get_table(&x).expect("Can't get :x"));
+
+  // Описатель массива другого выражения не принимается
+  let other = conn.prepare("begin null; end;").expect("Can't prepare PL/SQL block");
+  match other.get_table(&n) {
+    Err(Error::InvalidParam) => {},
+    r => panic!("Unexpected result: {:?}", r),
+  }
+}
+
+#[test]
+fn dbms_output() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+
+  assert_eq!(Vec::<String>::new(), conn.take_output().expect("Can't take disabled output"));
+
+  conn.enable_output(None).expect("Can't enable DBMS_OUTPUT");
+  // Больше строк, чем извлекается за один запрос, включая пустые
+  conn.prepare("begin for i in 1..70 loop dbms_output.put_line(case when mod(i, 10) = 0 then null else 'line ' || i end); end loop; end;")
+    .unwrap().execute().expect("Can't execute PL/SQL block");
+
+  let expected: Vec<String> = (1..71).map(|i| if i % 10 == 0 { String::new() } else { format!("line {}", i) }).collect();
+  assert_eq!(expected, conn.take_output().expect("Can't take output"));
+  assert_eq!(Vec::<String>::new(), conn.take_output().expect("Can't take empty output"));
+
+  conn.disable_output().expect("Can't disable DBMS_OUTPUT");
+}
+
+#[test]
+fn compile_errors() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+
+  let stmt = conn.prepare("create or replace procedure test_invalid_proc is\nbegin\n  undefined_proc;\nend;").expect("Can't prepare DDL");
+  match stmt.execute() {
+    Err(Error::Compile { object, diagnostics }) => {
+      assert_eq!("TEST_INVALID_PROC", object);
+      let error = diagnostics.iter().find(|d| d.code == 201).expect("PLS-00201 not found");
+      assert_eq!(3, error.line);
+      assert_eq!(3, error.column);
+      assert!(!error.warning);
+    },
+    r => panic!("Expected compilation error, got {:?}", r),
+  }
+  let diagnostics = conn.compile_diagnostics(None, "PROCEDURE", "TEST_INVALID_PROC").expect("Can't get diagnostics");
+  assert!(diagnostics.iter().any(|d| d.code == 201));
+
+  conn.prepare("drop procedure test_invalid_proc").unwrap().execute().expect("Can't drop procedure");
+
+  conn.prepare("create or replace package test_invalid_pkg is procedure p; end;").unwrap().execute().expect("Can't create package");
+  let body = "create or replace package body test_invalid_pkg is procedure p is begin undefined_proc; end; end;";
+  assert!(conn.prepare(body).unwrap().execute().is_err());
+  // Перекомпиляция тела указывает тип объекта после его имени
+  match conn.prepare("alter package test_invalid_pkg compile body").unwrap().execute() {
+    Err(Error::Compile { object, diagnostics }) => {
+      assert_eq!("TEST_INVALID_PKG", object);
+      assert!(diagnostics.iter().any(|d| d.code == 201));
+    },
+    r => panic!("Expected compilation error, got {:?}", r),
+  }
+  // Перекомпиляция без уточнения компилирует и тело, поэтому его ошибки тоже сообщаются
+  match conn.prepare("alter package test_invalid_pkg compile").unwrap().execute() {
+    Err(Error::Compile { object, diagnostics }) => {
+      assert_eq!("TEST_INVALID_PKG", object);
+      assert!(diagnostics.iter().any(|d| d.code == 201));
+    },
+    r => panic!("Expected compilation error, got {:?}", r),
+  }
+  conn.prepare("drop package test_invalid_pkg").unwrap().execute().expect("Can't drop package");
+}
+
+#[test]
+fn compile_warnings() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+  conn.prepare("alter session set plsql_warnings = 'ENABLE:ALL'").unwrap().execute().expect("Can't enable PL/SQL warnings");
+
+  // PLW-05018: unit omitted optional AUTHID clause
+  let stmt = conn.prepare("create or replace procedure test_warning_proc is\nbegin\n  null;\nend;").expect("Can't prepare DDL");
+  stmt.execute().expect("Procedure with warnings only must be created without error");
+  let warnings = stmt.compile_warnings();
+  assert!(warnings.iter().any(|d| d.code == 5018));
+  assert!(warnings.iter().all(|d| d.warning));
+
+  // Процедура без предупреждений очищает сообщения предыдущего выполнения
+  conn.prepare("alter session set plsql_warnings = 'DISABLE:ALL'").unwrap().execute().expect("Can't disable PL/SQL warnings");
+  stmt.execute().expect("Can't recreate procedure");
+  assert!(stmt.compile_warnings().is_empty());
+
+  conn.prepare("drop procedure test_warning_proc").unwrap().execute().expect("Can't drop procedure");
+}
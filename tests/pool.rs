@@ -0,0 +1,125 @@
+//! Тесты пулов сессий и соединений.
+
+extern crate oci;
+
+use std::thread;
+use std::time::Duration;
+
+use oci::Environment;
+use oci::error::Error;
+use oci::params::{ConnectionPoolParams, ConnectParams, Credentials, SessionPoolParams};
+use oci::pool::{ConnectionPool, SessionPool};
+use oci::types::{AttachMode, AuthMode, CreateMode};
+use oci::version::{client_version, Version};
+
+#[test]
+fn session_pool() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  // Пустые имя пользователя и пароль означают внешнюю аутентификацию
+  let pool = SessionPool::new(&env, SessionPoolParams {
+    min: 1,
+    max: 2,
+    increment: 1,
+    wait_timeout: Some(Duration::from_secs(0)),
+    ..Default::default()
+  }).expect("Can't create session pool");
+
+  assert!(!pool.name().is_empty());
+  assert_eq!(1, pool.min().expect("Can't get pool min"));
+  assert_eq!(2, pool.max().expect("Can't get pool max"));
+  assert_eq!(0, pool.busy_count().expect("Can't get busy count"));
+  {
+    let conn1 = pool.get().expect("Can't get first session");
+    let conn2 = pool.get().expect("Can't get second session");
+    assert_eq!(2, pool.busy_count().expect("Can't get busy count"));
+    assert_eq!(2, pool.open_count().expect("Can't get open count"));
+
+    // Пул исчерпан, а ожидать освобождения сессии не требуется
+    assert!(pool.get().is_err());
+
+    conn1.prepare("select * from dual").unwrap().query().expect("Can't execute query in first session");
+    conn2.prepare("select * from dual").unwrap().query().expect("Can't execute query in second session");
+  }
+  // Сессии вернулись в пул, но остались открытыми
+  assert_eq!(0, pool.busy_count().expect("Can't get busy count"));
+  assert_eq!(2, pool.open_count().expect("Can't get open count"));
+}
+
+#[test]
+fn session_pool_from_threads() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let pool = SessionPool::new(&env, SessionPoolParams {
+    min: 1,
+    max: 2,
+    increment: 1,
+    ..Default::default()
+  }).expect("Can't create session pool");
+
+  thread::scope(|s| {
+    let workers: Vec<_> = (0..2).map(|_| s.spawn(|| {
+      let conn = pool.get().expect("Can't get session in thread");
+      let mut stmt = conn.prepare("select 1 from dual").expect("Can't prepare query in thread");
+      let rs = stmt.query().expect("Can't execute query in thread");
+      let row = rs.next().expect("Can't fetch in thread").expect("Nothing fetch in thread");
+      let value: Option<u32> = row.get(0).expect("Can't get value in thread");
+      value
+    })).collect();
+    for worker in workers {
+      assert_eq!(Some(1), worker.join().unwrap());
+    }
+  });
+  assert_eq!(0, pool.busy_count().expect("Can't get busy count"));
+}
+#[test]
+fn session_pool_requires_threaded_environment() {
+  let env = Environment::new(CreateMode::default()).expect("Can't init ORACLE environment");
+  match SessionPool::new(&env, SessionPoolParams::default()) {
+    Err(Error::NotThreaded) => {},
+    res => panic!("Session pool requires threaded environment, but got {:?}", res),
+  };
+}
+#[test]
+fn session_pool_timed_wait() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let res = SessionPool::new(&env, SessionPoolParams {
+    wait_timeout: Some(Duration::from_millis(100)),
+    ..Default::default()
+  });
+  // Ожидание с таймаутом поддерживается только клиентом 12.2 и выше
+  if client_version() < Version::minor(12, 2) {
+    match res {
+      Err(Error::UnsupportedClient { .. }) => {},
+      res => panic!("Timed wait requires 12.2 client, but got {:?}", res),
+    }
+  } else {
+    res.expect("Can't create session pool with timed wait");
+  }
+}
+
+#[test]
+fn connection_pool() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let pool = ConnectionPool::new(&env, ConnectionPoolParams {
+    min: 1,
+    max: 2,
+    increment: 1,
+    ..Default::default()
+  }).expect("Can't create connection pool");
+
+  assert!(!pool.name().is_empty());
+  assert_eq!(1, pool.min().expect("Can't get pool min"));
+  assert_eq!(2, pool.max().expect("Can't get pool max"));
+
+  let conn1 = pool.connect(Credentials::Ext, AuthMode::SysDba).expect("Can't connect through pool");
+  // Подключение через окружение с указанием имени пула
+  let conn2 = env.connect(ConnectParams {
+    dblink: pool.name().into(),
+    attach_mode: AttachMode::CPool,
+    credentials: Credentials::Ext,
+    auth_mode: AuthMode::SysDba,
+  }).expect("Can't connect through environment in CPool mode");
+
+  conn1.prepare("select * from dual").unwrap().query().expect("Can't execute query in first session");
+  conn2.prepare("select * from dual").unwrap().query().expect("Can't execute query in second session");
+  assert!(pool.open_count().expect("Can't get open count") <= 2);
+}
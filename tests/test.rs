@@ -1,7 +1,12 @@
 extern crate oci;
 
+use std::thread;
+use std::time::Duration;
+
 use oci::Environment;
+use oci::error::Error;
 use oci::params::{ConnectParams, Credentials};
+use oci::reconnect::ReconnectingConnection;
 use oci::types::{AttachMode, AuthMode, CreateMode};
 
 mod utils;
@@ -66,3 +71,384 @@ fn can_prepare() {
   let stmt = conn.prepare("create table test").expect("Can't prepare invalid DDL expression");
   assert!(stmt.execute().is_err());
 }
+
+#[test]
+fn can_fetch_in_batches() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+
+  let mut stmt = conn.prepare("select level from dual connect by level <= 10").expect("Can't prepare SELECT expression");
+  stmt.set_fetch_size(4);
+  assert_eq!(4, stmt.fetch_size());
+
+  let rs = stmt.query().expect("Can't execute SELECT expression");
+  // Строки разных порций должны оставаться доступными одновременно
+  let rows: Vec<_> = (&rs).collect();
+  let values: Vec<u32> = rows.iter().map(|r| r.get(0).unwrap().unwrap()).collect();
+  assert_eq!((1..11).collect::<Vec<_>>(), values);
+  assert!(rs.next().unwrap().is_none());
+}
+
+#[test]
+fn can_prefetch() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+  conn.set_prefetch_rows(100);
+  conn.set_prefetch_memory(64 * 1024);
+
+  let mut stmt = conn.prepare("select level from dual connect by level <= 10").expect("Can't prepare SELECT expression");
+  // Умолчания соединения применяются к подготавливаемым выражениям
+  assert_eq!(100, stmt.prefetch_rows().expect("Can't get prefetch rows"));
+  assert_eq!(64 * 1024, stmt.prefetch_memory().expect("Can't get prefetch memory"));
+  {
+    let rs = stmt.query().expect("Can't execute SELECT expression");
+    assert_eq!(10, (&rs).count());
+  }
+  // Отключение получения строк заранее не влияет на результат
+  stmt.set_prefetch_rows(0).expect("Can't set prefetch rows");
+  stmt.set_prefetch_memory(0).expect("Can't set prefetch memory");
+  assert_eq!(0, stmt.prefetch_rows().expect("Can't get prefetch rows"));
+  assert_eq!(0, stmt.prefetch_memory().expect("Can't get prefetch memory"));
+  let rs = stmt.query().expect("Can't execute SELECT expression");
+  assert_eq!(10, (&rs).count());
+}
+#[test]
+fn can_scroll() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+
+  let mut stmt = conn.prepare("select level from dual connect by level <= 10").expect("Can't prepare SELECT expression");
+  let rs = stmt.query_scrollable().expect("Can't execute scrollable SELECT expression");
+
+  let get = |row: Option<oci::stmt::query::Row>| -> Option<u32> { row.expect("Row not exists").get(0).expect("Can't get value") };
+  assert_eq!(Some(10), get(rs.last().expect("Can't fetch last row")));
+  assert_eq!(10, rs.current_position().expect("Can't get position"));
+  assert_eq!(Some(1), get(rs.first().expect("Can't fetch first row")));
+  assert_eq!(Some(5), get(rs.absolute(5).expect("Can't fetch absolute row")));
+  assert_eq!(Some(3), get(rs.relative(-2).expect("Can't fetch relative row")));
+  assert_eq!(Some(2), get(rs.prior().expect("Can't fetch prior row")));
+  assert_eq!(Some(3), get(rs.next().expect("Can't fetch next row")));
+  assert_eq!(3, rs.current_position().expect("Can't get position"));
+  assert!(rs.absolute(11).expect("Can't fetch absolute row").is_none());
+}
+#[test]
+fn can_fetch_cursor_column() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+
+  let mut stmt = conn.prepare("select level, cursor(select level * 10 from dual connect by level <= 2) from dual connect by level <= 2").expect("Can't prepare SELECT expression");
+  let rs = stmt.query().expect("Can't execute SELECT expression");
+  let mut count = 0;
+  for row in &rs {
+    let level: u32 = row.get(0).expect("Can't get level").expect("level is NULL");
+    let mut nested = row.get_cursor(1).expect("Can't get cursor").expect("cursor is NULL");
+    let nested_rs = nested.query().expect("Can't get rows from cursor");
+    let values: Vec<Option<u32>> = (&nested_rs).map(|r| r.get(0).unwrap()).collect();
+    assert_eq!(vec![Some(10), Some(20)], values);
+    count += level;
+  }
+  assert_eq!(3, count);
+}
+#[test]
+fn can_describe_and_validate() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+
+  let stmt = conn.prepare("select 1 as one, 'two' as two from dual").expect("Can't prepare SELECT expression");
+  stmt.validate().expect("Can't validate SELECT expression");
+  let names: Vec<String> = stmt.describe().expect("Can't describe SELECT expression").into_iter().map(|c| c.name).collect();
+  assert_eq!(vec!["ONE".to_owned(), "TWO".to_owned()], names);
+
+  let stmt = conn.prepare("select * form dual").expect("Can't prepare invalid expression");
+  match stmt.validate() {
+    // ORA-00923: FROM keyword not found where expected
+    Err(oci::error::Error::Parse { offset, info }) => {
+      assert_eq!(923, info.code);
+      assert!(offset > 0);
+    },
+    r => panic!("Unexpected result: {:?}", r),
+  }
+  assert!(stmt.describe().is_err());
+
+  // Ошибка в первой лексеме сообщается с нулевым смещением
+  let stmt = conn.prepare("selec 1 from dual").expect("Can't prepare invalid expression");
+  match stmt.validate() {
+    // ORA-00900: invalid SQL statement
+    Err(oci::error::Error::Parse { offset: 0, info }) => assert_eq!(900, info.code),
+    r => panic!("Unexpected result: {:?}", r),
+  }
+}
+#[test]
+fn can_get_bind_names() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+
+  let stmt = conn.prepare("select 1 from dual").expect("Can't prepare SELECT expression");
+  assert!(stmt.bind_names().expect("Can't get bind names").is_empty());
+
+  let mut stmt = conn.prepare("select :a, :b, :a, :c from dual").expect("Can't prepare SELECT expression");
+  unsafe { stmt.bind(":b", &1i32).expect("Can't bind :b"); }
+  let names = stmt.bind_names().expect("Can't get bind names");
+  let names: Vec<_> = names.into_iter().map(|b| (b.name, b.pos, b.duplicated, b.bound)).collect();
+  assert_eq!(vec![
+    ("A".to_owned(), 0, true, false),
+    ("B".to_owned(), 1, false, true),
+    ("C".to_owned(), 3, false, false),
+  ], names);
+}
+#[test]
+fn can_bind_params() {
+  use std::collections::HashMap;
+  use oci::stmt::params::ToParam;
+
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_params (id number, name varchar2(10))").unwrap().execute().expect("Can't create table");
+
+  {
+    let mut stmt = conn.prepare("insert into test_params values (:id, :name)").expect("Can't prepare INSERT");
+    let name = "one".to_owned();
+    assert_eq!(1, stmt.execute_with(&(&1, &name)).expect("Can't insert tuple"));
+    let params: Vec<&ToParam> = vec![&2, &"two"];
+    assert_eq!(1, stmt.execute_with(&params).expect("Can't insert slice"));
+    let mut params: HashMap<&str, &ToParam> = HashMap::new();
+    params.insert(":id", &3);
+    params.insert(":name", &None::<&str>);
+    assert_eq!(1, stmt.execute_with(&params).expect("Can't insert map"));
+  }
+  {
+    let mut stmt = conn.prepare("select name from test_params where id between :1 and :2 order by id").expect("Can't prepare SELECT");
+    let rs = stmt.query_with(&[&2, &3]).expect("Can't execute SELECT");
+    let names: Vec<Option<String>> = (&rs).map(|r| r.get(0).unwrap()).collect();
+    assert_eq!(vec![Some("two".to_owned()), None], names);
+  }
+  {
+    let mut stmt = conn.prepare("begin :x := :x + 1; :s := null; end;").expect("Can't prepare PL/SQL block");
+    let (x, s) = (1i32, "text".to_owned());
+    // В PL/SQL блоке переменные являются IN OUT, но сервер изменяет только копии значений
+    stmt.execute_with(&(&x, &s)).expect("Can't execute PL/SQL block");
+    assert_eq!((1, "text"), (x, s.as_str()));
+    // Лишний параметр не связывается, а уже связанные отвязываются
+    assert!(stmt.execute_with(&(&x, &s, &3)).is_err());
+    stmt.execute_with(&(&x, &s)).expect("Can't execute PL/SQL block after bind error");
+  }
+
+  conn.prepare("drop table test_params").unwrap().execute().expect("Can't drop table");
+}
+#[test]
+fn can_bind_reader() {
+  use std::io::{self, Cursor, ErrorKind, Read};
+
+  struct FailingReader;
+  impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+      Err(io::Error::new(ErrorKind::Other, "read failed"))
+    }
+  }
+
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_reader (id number, b blob, c clob)").unwrap().execute().expect("Can't create table");
+
+  // Несколько полных порций и одна неполная, чтобы задействовать все виды частей
+  let data: Vec<u8> = (0..200000).map(|i| b'a' + (i % 26) as u8).collect();
+  {
+    let mut stmt = conn.prepare("insert into test_reader values (1, :1, :2)").expect("Can't prepare INSERT");
+    stmt.bind_reader(1, Cursor::new(data.clone())).expect("Can't bind BLOB reader");
+    stmt.bind_text_reader(2, Cursor::new(data.clone())).expect("Can't bind CLOB reader");
+    assert_eq!(1, stmt.execute().expect("Can't insert from reader"));
+
+    let mut stmt = conn.prepare("insert into test_reader values (2, :1, null)").expect("Can't prepare INSERT");
+    stmt.bind_reader(1, Cursor::new(vec![1, 2, 3])).expect("Can't bind small reader");
+    assert_eq!(1, stmt.execute().expect("Can't insert from small reader"));
+
+    // Ошибка читателя возвращается вместо ошибки OCI, прервавшей выполнение
+    let mut stmt = conn.prepare("insert into test_reader values (3, :1, null)").expect("Can't prepare INSERT");
+    let failing = Cursor::new(vec![1, 2, 3]).chain(FailingReader);
+    stmt.bind_reader(1, failing).expect("Can't bind failing reader");
+    match stmt.execute() {
+      Err(oci::error::Error::Io(ref e)) => assert_eq!(ErrorKind::Other, e.kind()),
+      r => panic!("Unexpected result: {:?}", r),
+    }
+  }
+  {
+    let mut stmt = conn.prepare("select dbms_lob.getlength(b), dbms_lob.getlength(c) from test_reader order by id").expect("Can't prepare SELECT");
+    let rs = stmt.query().expect("Can't execute SELECT");
+    let lens: Vec<(Option<u32>, Option<u32>)> = (&rs).map(|r| (r.get(0).unwrap(), r.get(1).unwrap())).collect();
+    assert_eq!(vec![(Some(200000), Some(200000)), (Some(3), None)], lens);
+  }
+
+  conn.prepare("drop table test_reader").unwrap().execute().expect("Can't drop table");
+}
+#[test]
+fn can_fetch_long() {
+  use std::io::Cursor;
+
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_long (id number, l long)").unwrap().execute().expect("Can't create LONG table");
+  conn.prepare("create table test_long_raw (id number, r long raw)").unwrap().execute().expect("Can't create LONG RAW table");
+
+  // Значение больше одной части, запрашиваемой при извлечении
+  let data: Vec<u8> = (0..200000).map(|i| b'a' + (i % 26) as u8).collect();
+  {
+    let mut stmt = conn.prepare("insert into test_long values (1, :1)").expect("Can't prepare INSERT");
+    stmt.bind_text_reader(1, Cursor::new(data.clone())).expect("Can't bind LONG reader");
+    assert_eq!(1, stmt.execute().expect("Can't insert LONG"));
+    conn.prepare("insert into test_long values (2, 'short')").unwrap().execute().expect("Can't insert short LONG");
+    conn.prepare("insert into test_long values (3, null)").unwrap().execute().expect("Can't insert NULL LONG");
+
+    let mut stmt = conn.prepare("insert into test_long_raw values (1, :1)").expect("Can't prepare INSERT");
+    stmt.bind_reader(1, Cursor::new(data.clone())).expect("Can't bind LONG RAW reader");
+    assert_eq!(1, stmt.execute().expect("Can't insert LONG RAW"));
+  }
+  {
+    let mut stmt = conn.prepare("select l from test_long order by id").expect("Can't prepare SELECT");
+    let rs = stmt.query().expect("Can't execute SELECT");
+    let values: Vec<Option<String>> = (&rs).map(|r| r.get(0).unwrap()).collect();
+    assert_eq!(vec![Some(String::from_utf8(data.clone()).unwrap()), Some("short".to_owned()), None], values);
+  }
+  {
+    let mut stmt = conn.prepare("select r from test_long_raw").expect("Can't prepare SELECT");
+    let rs = stmt.query().expect("Can't execute SELECT");
+    let row = rs.next().expect("Can't fetch LONG RAW").expect("Nothing fetch");
+    assert_eq!(Some(data), row.get(0).expect("Can't get LONG RAW"));
+  }
+
+  conn.prepare("drop table test_long").unwrap().execute().expect("Can't drop LONG table");
+  conn.prepare("drop table test_long_raw").unwrap().execute().expect("Can't drop LONG RAW table");
+}
+#[test]
+fn can_cache_statements() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+  assert_eq!(0, conn.statement_cache_size().expect("Can't get statement cache size"));
+  conn.set_statement_cache_size(5).expect("Can't set statement cache size");
+  assert_eq!(5, conn.statement_cache_size().expect("Can't get statement cache size"));
+
+  let sql = "select :1 + 1 from dual";
+  for i in 0..3 {
+    let mut stmt = conn.prepare_cached(sql).expect("Can't prepare cached statement");
+    let rs = stmt.query_with(&[&i]).expect("Can't execute cached statement");
+    let row = rs.next().expect("Can't fetch").expect("Nothing fetch");
+    assert_eq!(Some(i + 1), row.get(0).expect("Can't get value"));
+  }
+  {
+    let stmt = conn.prepare_with_key("test key", "select 1 from dual").expect("Can't prepare keyed statement");
+    assert_eq!(1, stmt.describe().expect("Can't describe keyed statement").len());
+  }
+  // По ключу выражение извлекается из кеша, текст не используется
+  {
+    let stmt = conn.prepare_with_key("test key", "").expect("Can't get keyed statement from cache");
+    assert_eq!(1, stmt.describe().expect("Can't describe cached statement").len());
+  }
+
+  assert!(conn.evict_cached(sql).expect("Can't evict statement"));
+  assert!(!conn.evict_cached(sql).expect("Can't evict missing statement"));
+  conn.prepare_with_key("test key", "").expect("Can't get keyed statement from cache").evict();
+  assert!(!conn.evict_cached("test key").expect("Can't evict missing statement"));
+}
+#[test]
+fn can_close_explicitly() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  {
+    let conn = utils::connect(&env);
+    conn.server_version().expect("Can't get server version");
+    conn.close().expect("Can't close connection");
+  }
+  env.close().expect("Can't close environment");
+}
+#[test]
+fn can_check_liveness() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let conn = utils::connect(&env);
+  conn.ping().expect("Can't ping server");
+  assert!(conn.is_healthy().expect("Can't get server status"));
+}
+#[test]
+fn can_reconnect() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let params = ConnectParams {
+    dblink: "".into(),
+    attach_mode: AttachMode::default(),
+    credentials: Credentials::Ext,
+    auth_mode: AuthMode::SysDba,
+  };
+  let mut conn = ReconnectingConnection::new(&env, params).expect("Can't connect to ORACLE database");
+  let select = |stmt: &mut oci::stmt::Statement| {
+    let rs = try!(stmt.query());
+    let row = try!(rs.next()).expect("Nothing fetch");
+    row.get::<i32, _>(0)
+  };
+  assert_eq!(Some(1), conn.with_statement("select 1 from dual", &select).expect("Can't execute statement"));
+
+  conn.reconnect().expect("Can't reconnect");
+  assert!(conn.connection().is_healthy().expect("Can't get server status"));
+  assert_eq!(Some(1), conn.with_statement("select 1 from dual", &select).expect("Can't execute statement after reconnect"));
+}
+#[test]
+fn reconnect_keeps_settings() {
+  let env = Environment::new(CreateMode::default()).unwrap();
+  let params = ConnectParams {
+    dblink: "".into(),
+    attach_mode: AttachMode::default(),
+    credentials: Credentials::Ext,
+    auth_mode: AuthMode::SysDba,
+  };
+  let mut conn = ReconnectingConnection::new(&env, params).expect("Can't connect to ORACLE database");
+  conn.set_prefetch_rows(100);
+  conn.set_statement_cache_size(5).expect("Can't set statement cache size");
+  conn.enable_output(None).expect("Can't enable output");
+
+  conn.reconnect().expect("Can't reconnect");
+  assert_eq!(5, conn.connection().statement_cache_size().expect("Can't get statement cache size"));
+  let rows = conn.with_statement("select 1 from dual", |stmt| stmt.prefetch_rows()).expect("Can't prepare statement");
+  assert_eq!(100, rows);
+  let output = conn.run(|c| {
+    try!(try!(c.prepare("begin dbms_output.put_line('hello'); end;")).execute());
+    c.take_output()
+  }).expect("Can't get output after reconnect");
+  assert_eq!(vec!["hello".to_owned()], output);
+}
+#[test]
+fn can_cancel_from_other_thread() {
+  let env = Environment::new(CreateMode::Threaded).unwrap();
+  let conn = utils::connect(&env);
+  let handle = conn.cancel_handle().expect("Can't get cancel handle");
+  let canceller = thread::spawn(move || {
+    thread::sleep(Duration::from_secs(1));
+    handle.cancel().expect("Can't cancel query");
+  });
+  let stmt = conn.prepare("begin dbms_lock.sleep(10); end;").expect("Can't prepare query");
+  match stmt.execute() {
+    Err(Error::Cancelled) => {},
+    res => panic!("Query must be cancelled, but got {:?}", res),
+  }
+  canceller.join().unwrap();
+
+  // После отмены соединение остается пригодным для работы
+  let mut stmt = conn.prepare("select 1 from dual").expect("Can't prepare query after cancel");
+  let rs = stmt.query().expect("Can't execute query after cancel");
+  let row = rs.next().expect("Can't fetch").expect("Nothing fetch");
+  assert_eq!(Some(1), row.get(0).expect("Can't get value"));
+}
+#[test]
+fn cancel_handle_outlives_environment() {
+  {
+    let env = Environment::new(CreateMode::default()).unwrap();
+    let conn = utils::connect(&env);
+    match conn.cancel_handle() {
+      Err(Error::NotThreaded) => {},
+      res => panic!("Cancel handle requires threaded environment, but got {:?}", res),
+    }
+  }
+  let handle = {
+    let env = Environment::new(CreateMode::Threaded).unwrap();
+    let conn = utils::connect(&env);
+    let handle = conn.cancel_handle().expect("Can't get cancel handle");
+    // Забытое соединение не отсоединяет объект отмены, это делает окружение при разрушении
+    std::mem::forget(conn);
+    handle
+  };
+  handle.cancel().expect("Cancel after environment drop must do nothing");
+}
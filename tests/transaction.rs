@@ -0,0 +1,77 @@
+//! Тесты управления транзакциями.
+
+extern crate oci;
+
+use oci::{Connection, Environment};
+use oci::error::Error;
+use oci::types::CreateMode;
+mod utils;
+
+fn insert(conn: &Connection, id: u32) {
+  conn.prepare(&format!("insert into test_trans values ({})", id))
+      .expect("Can't prepare INSERT")
+      .execute()
+      .expect("Can't execute INSERT");
+}
+
+#[test]
+fn transaction() {
+  let env = Environment::new(CreateMode::Threaded).expect("Can't init ORACLE environment in THREADED mode");
+  let conn = utils::connect(&env);
+  conn.prepare("create table test_trans (id number)").unwrap().execute().expect("Can't create table");
+
+  // Незафиксированная транзакция откатывается при разрушении
+  {
+    let _tx = conn.transaction().expect("Can't start transaction");
+    insert(&conn, 1);
+    assert_eq!(1, utils::count(&conn, "test_trans"));
+  }
+  assert_eq!(0, utils::count(&conn, "test_trans"));
+
+  // Откат до точки сохранения
+  {
+    let tx = conn.transaction().expect("Can't start transaction");
+    insert(&conn, 1);
+    tx.savepoint("first").expect("Can't create savepoint");
+    insert(&conn, 2);
+    tx.rollback_to("first").expect("Can't rollback to savepoint");
+    tx.commit().expect("Can't commit");
+  }
+  assert_eq!(1, utils::count(&conn, "test_trans"));
+
+  // Явные фиксация и откат на соединении
+  insert(&conn, 3);
+  conn.rollback().expect("Can't rollback");
+  assert_eq!(1, utils::count(&conn, "test_trans"));
+  insert(&conn, 3);
+  conn.commit().expect("Can't commit");
+  assert_eq!(2, utils::count(&conn, "test_trans"));
+
+  match conn.transaction().expect("Can't start transaction").savepoint("bad name; drop table test_trans") {
+    Err(Error::InvalidIdentifier(_)) => {},
+    r => panic!("Unexpected result: {:?}", r),
+  }
+
+  // Пока существует объект транзакции, второй создать нельзя, как нельзя и завершить транзакцию в обход него.
+  // Разрушение объекта откатывает и изменения, сделанные до его создания
+  insert(&conn, 4);
+  {
+    let _tx = conn.transaction().expect("Can't start transaction");
+    match conn.transaction() {
+      Err(Error::TransactionInProgress) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+    match conn.commit() {
+      Err(Error::TransactionInProgress) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+    match conn.rollback() {
+      Err(Error::TransactionInProgress) => {},
+      r => panic!("Unexpected result: {:?}", r),
+    }
+  }
+  assert_eq!(2, utils::count(&conn, "test_trans"));
+  conn.transaction().expect("Can't start transaction after previous one finished");
+
+  conn.prepare("drop table test_trans").unwrap().execute().expect("Can't drop table");
+}
@@ -2,6 +2,7 @@
 use oci::{Environment, Connection};
 use oci::params::{ConnectParams, Credentials};
 use oci::types::AuthMode;
+use oci::version::{client_version, Version};
 
 pub fn connect<'e>(env: &'e Environment) -> Connection<'e> {
   let params = ConnectParams {
@@ -11,4 +12,18 @@ pub fn connect<'e>(env: &'e Environment) -> Connection<'e> {
     auth_mode: AuthMode::SysDba,
   };
   env.connect(params).expect("Can't connect to ORACLE database")
-}
\ No newline at end of file
+}
+/// Получает количество строк в указанной таблице.
+#[allow(dead_code)]// Используется не всеми тестами
+pub fn count(conn: &Connection, table: &str) -> u32 {
+  let mut stmt = conn.prepare(&format!("select count(*) from {}", table)).expect("Can't prepare query");
+  let rs = stmt.query().expect("Can't execute query");
+  let row = rs.next().expect("Can't fetch").expect("Nothing fetch");
+  row.get(0).expect("Can't get count").expect("count(*) is NULL")
+}
+/// Проверяет, что и клиент, и сервер имеют версию не ниже указанной.
+#[allow(dead_code)]// Используется не всеми тестами
+pub fn supports(conn: &Connection, major: i32, minor: i32) -> bool {
+  let version = Version::minor(major, minor);
+  client_version() >= version && conn.server_version().expect("Can't get server version") >= version
+}